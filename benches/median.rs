@@ -0,0 +1,71 @@
+// Copyright © 2019 Bader Alshaya
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Compare the selection-based median against sorting a copy
+//! of the input. Run with `cargo bench`.
+
+use std::time::{Duration, Instant};
+
+/// Median by sorting a copy of the input, as `stats::median`
+/// used to do.
+fn sort_median(nums: &[f64]) -> Option<f64> {
+    let mut nums = nums.to_owned();
+    nums.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    if nums.is_empty() {
+        None
+    } else {
+        Some(nums[(nums.len() - 1) / 2])
+    }
+}
+
+/// Pseudo-random values in [0, 1) from a xorshift
+/// generator, so that runs are repeatable.
+fn random_values(n: usize) -> Vec<f64> {
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    (0..n)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state >> 11) as f64 / (1u64 << 53) as f64
+        })
+        .collect()
+}
+
+/// Best time of several runs of `f`.
+fn time(runs: usize, mut f: impl FnMut() -> Option<f64>) -> Duration {
+    (0..runs)
+        .map(|_| {
+            let start = Instant::now();
+            std::hint::black_box(f());
+            start.elapsed()
+        })
+        .min()
+        .unwrap()
+}
+
+fn main() {
+    for &n in &[1_000, 100_000, 10_000_000] {
+        let nums = random_values(n);
+        let runs = if n > 1_000_000 { 3 } else { 20 };
+        assert_eq!(sort_median(&nums), stats::median(&nums));
+
+        let sort = time(runs, || sort_median(&nums));
+        let select = time(runs, || stats::median(&nums));
+        let mut scratch = nums.clone();
+        let in_place = time(runs, || {
+            scratch.copy_from_slice(&nums);
+            stats::median_in_place(&mut scratch, stats::MedianPolicy::Lower)
+        });
+        println!(
+            "n = {:>10}: sort {:>12?}  select {:>12?}  select in place {:>12?}  ({:.1}x)",
+            n,
+            sort,
+            select,
+            in_place,
+            sort.as_secs_f64() / select.as_secs_f64(),
+        );
+    }
+}
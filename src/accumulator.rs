@@ -0,0 +1,732 @@
+// Copyright © 2019 Bader Alshaya
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Incremental accumulators that compute statistics one
+//! value at a time in constant memory.
+
+use crate::{Numeric, StatsError};
+
+/// A statistic that is computed incrementally. Values are
+/// pushed one at a time, and the current result can be
+/// queried at any point. The result follows the same
+/// contract as the corresponding [`StatFn`](crate::StatFn):
+/// `None` means the statistic is ill-defined for the values
+/// seen so far.
+pub trait Accumulator {
+    /// Add one value to the accumulator.
+    fn push(&mut self, x: f64);
+
+    /// Push every value of `nums`, which may be of any
+    /// [`Numeric`] type.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use stats::*;
+    /// let mut acc = RunningMean::default();
+    /// acc.push_all(&[1u32, 2, 3]);
+    /// acc.push_all(vec![4.0f32]);
+    /// assert_eq!(Some(2.5), acc.result());
+    /// ```
+    fn push_all<I>(&mut self, nums: I)
+    where
+        Self: Sized,
+        I: IntoIterator,
+        I::Item: Numeric,
+    {
+        for x in nums {
+            self.push(x.to_f64());
+        }
+    }
+
+    /// Current value of the statistic, or why it is
+    /// undefined. An accumulator cannot look back at the
+    /// values pushed into it, so it never reports
+    /// [`StatsError::ContainsNan`]: NaN values propagate into
+    /// the result. Apply a [`NanPolicy`](crate::NanPolicy) to
+    /// values before pushing them to treat NaN otherwise.
+    fn try_result(&self) -> Result<f64, StatsError>;
+
+    /// Current value of the statistic.
+    fn result(&self) -> Option<f64> {
+        self.try_result().ok()
+    }
+
+    /// Combine the values seen by `other` into `self`, as if
+    /// they had all been pushed into `self`.
+    fn merge(&mut self, other: &Self)
+    where
+        Self: Sized;
+}
+
+/// Push all of `nums` into `acc` and return its result.
+pub(crate) fn accumulate<A: Accumulator>(mut acc: A, nums: &[f64]) -> Option<f64> {
+    acc.push_all(nums);
+    acc.result()
+}
+
+/// Sum with Neumaier's compensation: the rounding error of
+/// each addition is collected separately and added back at
+/// the end.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct CompensatedSum {
+    sum: f64,
+    compensation: f64,
+}
+
+impl CompensatedSum {
+    /// Add `x` to the sum.
+    pub(crate) fn add(&mut self, x: f64) {
+        let t = self.sum + x;
+        if self.sum.abs() >= x.abs() {
+            self.compensation += (self.sum - t) + x;
+        } else {
+            self.compensation += (x - t) + self.sum;
+        }
+        self.sum = t;
+    }
+
+    /// Add another compensated sum to this one.
+    pub(crate) fn merge(&mut self, other: &Self) {
+        self.add(other.sum);
+        self.add(other.compensation);
+    }
+
+    /// Current value of the sum. Once the sum has become
+    /// infinite or NaN the compensation is meaningless, so it
+    /// is dropped.
+    pub(crate) fn value(&self) -> f64 {
+        if self.sum.is_finite() {
+            self.sum + self.compensation
+        } else {
+            self.sum
+        }
+    }
+}
+
+/// Running arithmetic mean, using compensated summation.
+/// Agrees with [`mean`](crate::mean).
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// let mut acc = RunningMean::default();
+/// assert_eq!(Some(0.0), acc.result());
+/// acc.push(-1.0);
+/// acc.push(1.0);
+/// assert_eq!(Some(0.0), acc.result());
+/// ```
+/// ```
+/// # use stats::*;
+/// let mut left = RunningMean::default();
+/// left.push(1.0);
+/// let mut right = RunningMean::default();
+/// right.push(2.0);
+/// right.push(3.0);
+/// left.merge(&right);
+/// assert_eq!(Some(2.0), left.result());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RunningMean {
+    count: u64,
+    sum: CompensatedSum,
+}
+
+impl Accumulator for RunningMean {
+    fn push(&mut self, x: f64) {
+        self.count += 1;
+        self.sum.add(x);
+    }
+
+    fn try_result(&self) -> Result<f64, StatsError> {
+        if self.count == 0 {
+            Ok(0.0)
+        } else {
+            Ok(self.sum.value() / self.count as f64)
+        }
+    }
+
+    fn merge(&mut self, other: &Self) {
+        self.count += other.count;
+        self.sum.merge(&other.sum);
+    }
+}
+
+/// Running geometric mean, computed as the exponential of
+/// the mean logarithm so that long inputs do not overflow.
+/// Agrees with [`geometric_mean`](crate::geometric_mean).
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// let mut acc = RunningGeometricMean::default();
+/// assert_eq!(Err(StatsError::EmptyInput), acc.try_result());
+/// acc.push_all(&[1e300; 1000]);
+/// assert!((acc.result().unwrap() / 1e300 - 1.0).abs() < 1e-12);
+/// acc.push(-1.0);
+/// assert_eq!(Err(StatsError::NegativeValue), acc.try_result());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RunningGeometricMean {
+    count: u64,
+    log_sum: CompensatedSum,
+    negative: bool,
+}
+
+impl Accumulator for RunningGeometricMean {
+    fn push(&mut self, x: f64) {
+        self.count += 1;
+        self.negative |= x < 0.0;
+        self.log_sum.add(x.ln());
+    }
+
+    fn try_result(&self) -> Result<f64, StatsError> {
+        StatsError::check_len(self.count as usize, 1)?;
+        if self.negative {
+            return Err(StatsError::NegativeValue);
+        }
+        Ok((self.log_sum.value() / self.count as f64).exp())
+    }
+
+    fn merge(&mut self, other: &Self) {
+        self.count += other.count;
+        self.negative |= other.negative;
+        self.log_sum.merge(&other.log_sum);
+    }
+}
+
+/// Running harmonic mean: the reciprocal of the mean
+/// reciprocal. Agrees with
+/// [`harmonic_mean`](crate::harmonic_mean).
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// let mut acc = RunningHarmonicMean::default();
+/// acc.push_all(&[1.0, 4.0, 4.0]);
+/// assert_eq!(Some(2.0), acc.result());
+/// acc.push(0.0);
+/// assert_eq!(Some(0.0), acc.result());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RunningHarmonicMean {
+    count: u64,
+    reciprocal_sum: CompensatedSum,
+    negative: bool,
+}
+
+impl Accumulator for RunningHarmonicMean {
+    fn push(&mut self, x: f64) {
+        self.count += 1;
+        self.negative |= x < 0.0;
+        self.reciprocal_sum.add(x.recip());
+    }
+
+    fn try_result(&self) -> Result<f64, StatsError> {
+        StatsError::check_len(self.count as usize, 1)?;
+        if self.negative {
+            return Err(StatsError::NegativeValue);
+        }
+        Ok(self.count as f64 / self.reciprocal_sum.value())
+    }
+
+    fn merge(&mut self, other: &Self) {
+        self.count += other.count;
+        self.negative |= other.negative;
+        self.reciprocal_sum.merge(&other.reciprocal_sum);
+    }
+}
+
+/// Running variance, using Welford's update and Chan's
+/// formula for merging. The default accumulator computes the
+/// population variance and agrees with
+/// [`variance`](crate::variance); the one made by
+/// [`RunningVariance::sample`] computes the sample variance
+/// and agrees with [`sample_variance`](crate::sample_variance).
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// let mut acc = RunningVariance::default();
+/// assert_eq!(None, acc.result());
+/// acc.push(1.0);
+/// assert_eq!(Some(0.0), acc.result());
+/// acc.push(3.0);
+/// assert_eq!(Some(1.0), acc.result());
+/// ```
+/// ```
+/// # use stats::*;
+/// let mut acc = RunningVariance::sample();
+/// acc.push(1.0);
+/// assert_eq!(
+///     Err(StatsError::NotEnoughSamples { needed: 2, got: 1 }),
+///     acc.try_result()
+/// );
+/// acc.push(3.0);
+/// assert_eq!(Some(2.0), acc.result());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RunningVariance {
+    count: u64,
+    mean: f64,
+    m2: f64,
+    sample: bool,
+}
+
+impl RunningVariance {
+    /// Accumulator for the sample (Bessel-corrected)
+    /// variance.
+    pub fn sample() -> Self {
+        RunningVariance {
+            sample: true,
+            ..Default::default()
+        }
+    }
+}
+
+impl Accumulator for RunningVariance {
+    fn push(&mut self, x: f64) {
+        self.count += 1;
+        let delta = x - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (x - self.mean);
+    }
+
+    fn try_result(&self) -> Result<f64, StatsError> {
+        // Degrees of freedom lost to estimating the mean.
+        let ddof = if self.sample { 1 } else { 0 };
+        StatsError::check_len(self.count as usize, ddof as usize + 1)?;
+        Ok(self.m2 / (self.count - ddof) as f64)
+    }
+
+    fn merge(&mut self, other: &Self) {
+        if other.count == 0 {
+            return;
+        }
+        if self.count == 0 {
+            self.count = other.count;
+            self.mean = other.mean;
+            self.m2 = other.m2;
+            return;
+        }
+        let count = self.count + other.count;
+        let delta = other.mean - self.mean;
+        let (n1, n2, n) = (self.count as f64, other.count as f64, count as f64);
+        self.mean += delta * n2 / n;
+        self.m2 += other.m2 + delta * delta * n1 * n2 / n;
+        self.count = count;
+    }
+}
+
+/// Running standard deviation: the square root of a
+/// [`RunningVariance`]. The default accumulator computes the
+/// population standard deviation and agrees with
+/// [`stddev`](crate::stddev); the one made by
+/// [`RunningStddev::sample`] agrees with
+/// [`sample_stddev`](crate::sample_stddev).
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// let mut acc = RunningStddev::default();
+/// assert_eq!(None, acc.result());
+/// acc.push(1.0);
+/// assert_eq!(Some(0.0), acc.result());
+/// acc.push(3.0);
+/// assert_eq!(Some(1.0), acc.result());
+/// ```
+/// ```
+/// # use stats::*;
+/// let mut left = RunningStddev::default();
+/// left.push(2.0);
+/// left.push(4.0);
+/// let mut right = RunningStddev::default();
+/// for &x in &[4.0, 4.0, 5.0, 5.0, 7.0, 9.0] {
+///     right.push(x);
+/// }
+/// left.merge(&right);
+/// assert_eq!(Some(2.0), left.result());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RunningStddev {
+    variance: RunningVariance,
+}
+
+impl RunningStddev {
+    /// Accumulator for the sample (Bessel-corrected)
+    /// standard deviation.
+    pub fn sample() -> Self {
+        RunningStddev {
+            variance: RunningVariance::sample(),
+        }
+    }
+}
+
+impl Accumulator for RunningStddev {
+    fn push(&mut self, x: f64) {
+        self.variance.push(x);
+    }
+
+    fn try_result(&self) -> Result<f64, StatsError> {
+        self.variance.try_result().map(f64::sqrt)
+    }
+
+    fn merge(&mut self, other: &Self) {
+        self.variance.merge(&other.variance);
+    }
+}
+
+/// Running count, mean and sums of the second to fourth
+/// powers of deviations from the mean, using the one-pass
+/// update and merge formulas of Pébay ("Formulas for Robust,
+/// One-Pass Parallel Computation of Covariances and
+/// Arbitrary-Order Statistical Moments", 2008).
+#[derive(Debug, Clone, Copy, Default)]
+struct Moments {
+    count: u64,
+    mean: f64,
+    m2: f64,
+    m3: f64,
+    m4: f64,
+}
+
+impl Moments {
+    fn push(&mut self, x: f64) {
+        let n1 = self.count as f64;
+        self.count += 1;
+        let n = self.count as f64;
+        let delta = x - self.mean;
+        let delta_n = delta / n;
+        let delta_n2 = delta_n * delta_n;
+        let term = delta * delta_n * n1;
+        self.mean += delta_n;
+        self.m4 += term * delta_n2 * (n * n - 3.0 * n + 3.0) + 6.0 * delta_n2 * self.m2
+            - 4.0 * delta_n * self.m3;
+        self.m3 += term * delta_n * (n - 2.0) - 3.0 * delta_n * self.m2;
+        self.m2 += term;
+    }
+
+    fn merge(&mut self, other: &Self) {
+        if other.count == 0 {
+            return;
+        }
+        if self.count == 0 {
+            *self = *other;
+            return;
+        }
+        let (a, b) = (*self, *other);
+        let (na, nb) = (a.count as f64, b.count as f64);
+        let n = na + nb;
+        let delta = b.mean - a.mean;
+        let delta2 = delta * delta;
+        self.count = a.count + b.count;
+        self.mean = a.mean + delta * nb / n;
+        self.m2 = a.m2 + b.m2 + delta2 * na * nb / n;
+        self.m3 = a.m3
+            + b.m3
+            + delta2 * delta * na * nb * (na - nb) / (n * n)
+            + 3.0 * delta * (na * b.m2 - nb * a.m2) / n;
+        self.m4 = a.m4
+            + b.m4
+            + delta2 * delta2 * na * nb * (na * na - na * nb + nb * nb) / (n * n * n)
+            + 6.0 * delta2 * (na * na * b.m2 + nb * nb * a.m2) / (n * n)
+            + 4.0 * delta * (na * b.m3 - nb * a.m3) / n;
+    }
+
+    /// Check that there are at least `needed` values, and
+    /// that they are not all equal.
+    fn check(&self, needed: usize) -> Result<(), StatsError> {
+        StatsError::check_len(self.count as usize, needed)?;
+        if self.m2 == 0.0 {
+            return Err(StatsError::ZeroVariance);
+        }
+        Ok(())
+    }
+
+    /// Population skewness.
+    fn skewness(&self) -> f64 {
+        let n = self.count as f64;
+        n.sqrt() * self.m3 / self.m2.powf(1.5)
+    }
+
+    /// Population excess kurtosis.
+    fn kurtosis(&self) -> f64 {
+        let n = self.count as f64;
+        n * self.m4 / (self.m2 * self.m2) - 3.0
+    }
+}
+
+/// Running skewness, using Pébay's one-pass formulas. The
+/// default accumulator computes the population skewness and
+/// agrees with [`skewness`](crate::skewness); the one made by
+/// [`RunningSkewness::sample`] agrees with
+/// [`sample_skewness`](crate::sample_skewness).
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// let mut acc = RunningSkewness::default();
+/// acc.push_all(&[1.0, 2.0, 3.0]);
+/// assert_eq!(Some(0.0), acc.result());
+/// acc.push(10.0);
+/// assert!(acc.result().unwrap() > 1.0);
+/// ```
+/// ```
+/// # use stats::*;
+/// let mut left = RunningSkewness::sample();
+/// left.push_all(&[1.0, 2.0]);
+/// let mut right = RunningSkewness::sample();
+/// right.push_all(&[4.0, 8.0, 16.0]);
+/// left.merge(&right);
+/// let all = sample_skewness(&[1.0, 2.0, 4.0, 8.0, 16.0]).unwrap();
+/// assert!((left.result().unwrap() - all).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RunningSkewness {
+    moments: Moments,
+    sample: bool,
+}
+
+impl RunningSkewness {
+    /// Accumulator for the bias-corrected sample skewness.
+    pub fn sample() -> Self {
+        RunningSkewness {
+            sample: true,
+            ..Default::default()
+        }
+    }
+}
+
+impl Accumulator for RunningSkewness {
+    fn push(&mut self, x: f64) {
+        self.moments.push(x);
+    }
+
+    fn try_result(&self) -> Result<f64, StatsError> {
+        if !self.sample {
+            self.moments.check(1)?;
+            return Ok(self.moments.skewness());
+        }
+        self.moments.check(3)?;
+        let n = self.moments.count as f64;
+        Ok(self.moments.skewness() * (n * (n - 1.0)).sqrt() / (n - 2.0))
+    }
+
+    fn merge(&mut self, other: &Self) {
+        self.moments.merge(&other.moments);
+    }
+}
+
+/// Running excess kurtosis, using Pébay's one-pass
+/// formulas. The default accumulator computes the
+/// population excess kurtosis and agrees with
+/// [`kurtosis`](crate::kurtosis); the one made by
+/// [`RunningKurtosis::sample`] agrees with
+/// [`sample_kurtosis`](crate::sample_kurtosis).
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// let mut acc = RunningKurtosis::default();
+/// acc.push_all(&[-1.0, 1.0]);
+/// assert_eq!(Some(-2.0), acc.result());
+/// ```
+/// ```
+/// # use stats::*;
+/// let mut acc = RunningKurtosis::sample();
+/// acc.push_all(&[1.0, 2.0, 3.0]);
+/// assert_eq!(
+///     Err(StatsError::NotEnoughSamples { needed: 4, got: 3 }),
+///     acc.try_result()
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RunningKurtosis {
+    moments: Moments,
+    sample: bool,
+}
+
+impl RunningKurtosis {
+    /// Accumulator for the bias-corrected sample excess
+    /// kurtosis.
+    pub fn sample() -> Self {
+        RunningKurtosis {
+            sample: true,
+            ..Default::default()
+        }
+    }
+}
+
+impl Accumulator for RunningKurtosis {
+    fn push(&mut self, x: f64) {
+        self.moments.push(x);
+    }
+
+    fn try_result(&self) -> Result<f64, StatsError> {
+        if !self.sample {
+            self.moments.check(1)?;
+            return Ok(self.moments.kurtosis());
+        }
+        self.moments.check(4)?;
+        let n = self.moments.count as f64;
+        let g2 = self.moments.kurtosis();
+        Ok(((n + 1.0) * g2 + 6.0) * (n - 1.0) / ((n - 2.0) * (n - 3.0)))
+    }
+
+    fn merge(&mut self, other: &Self) {
+        self.moments.merge(&other.moments);
+    }
+}
+
+/// Running L1 norm, using compensated summation. Agrees
+/// with [`l1`](crate::l1).
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// let mut acc = RunningL1::default();
+/// assert_eq!(Some(0.0), acc.result());
+/// acc.push(-3.0);
+/// acc.push(4.0);
+/// assert_eq!(Some(7.0), acc.result());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RunningL1 {
+    sum: CompensatedSum,
+}
+
+impl Accumulator for RunningL1 {
+    fn push(&mut self, x: f64) {
+        self.sum.add(x.abs());
+    }
+
+    fn try_result(&self) -> Result<f64, StatsError> {
+        Ok(self.sum.value())
+    }
+
+    fn merge(&mut self, other: &Self) {
+        self.sum.merge(&other.sum);
+    }
+}
+
+/// Running L2 norm. The squares are summed relative to the
+/// largest magnitude seen so far, which is rescaled whenever
+/// a larger one arrives, as in the BLAS `nrm2`. Agrees with
+/// [`l2`](crate::l2).
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// let mut acc = RunningL2::default();
+/// assert_eq!(Some(0.0), acc.result());
+/// acc.push(-3.0);
+/// acc.push(4.0);
+/// assert_eq!(Some(5.0), acc.result());
+/// ```
+/// ```
+/// # use stats::*;
+/// let mut left = RunningL2::default();
+/// left.push(3e200);
+/// let mut right = RunningL2::default();
+/// right.push(4e200);
+/// left.merge(&right);
+/// assert!((left.result().unwrap() / 5e200 - 1.0).abs() < 1e-15);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RunningL2 {
+    /// Largest magnitude seen so far.
+    scale: f64,
+    /// Sum of the squares of the values divided by `scale`.
+    sum_squares: f64,
+    /// Whether an infinite value has been seen.
+    infinite: bool,
+}
+
+impl Accumulator for RunningL2 {
+    fn push(&mut self, x: f64) {
+        let a = x.abs();
+        if a.is_infinite() {
+            self.infinite = true;
+        } else if a > self.scale {
+            let r = self.scale / a;
+            self.sum_squares = 1.0 + self.sum_squares * r * r;
+            self.scale = a;
+        } else if a != 0.0 {
+            // Also reached by NaN, which propagates.
+            let r = a / self.scale;
+            self.sum_squares += r * r;
+        }
+    }
+
+    fn try_result(&self) -> Result<f64, StatsError> {
+        let norm = self.scale * self.sum_squares.sqrt();
+        if self.infinite && !norm.is_nan() {
+            Ok(f64::INFINITY)
+        } else {
+            Ok(norm)
+        }
+    }
+
+    fn merge(&mut self, other: &Self) {
+        self.infinite |= other.infinite;
+        if other.scale > self.scale {
+            let r = self.scale / other.scale;
+            self.sum_squares = other.sum_squares + self.sum_squares * r * r;
+            self.scale = other.scale;
+        } else if other.scale != 0.0 {
+            let r = other.scale / self.scale;
+            self.sum_squares += other.sum_squares * r * r;
+        } else {
+            // Only zeros or NaN were pushed into `other`.
+            self.sum_squares += other.sum_squares;
+        }
+    }
+}
+
+/// Running L∞ norm. Agrees with [`linf`](crate::linf).
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// let mut acc = RunningLinf::default();
+/// assert_eq!(Some(0.0), acc.result());
+/// acc.push(-4.0);
+/// acc.push(3.0);
+/// assert_eq!(Some(4.0), acc.result());
+/// acc.push(f64::NAN);
+/// acc.push(5.0);
+/// assert!(acc.result().unwrap().is_nan());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RunningLinf {
+    max: f64,
+}
+
+impl Accumulator for RunningLinf {
+    fn push(&mut self, x: f64) {
+        let a = x.abs();
+        if a > self.max || a.is_nan() {
+            self.max = a;
+        }
+    }
+
+    fn try_result(&self) -> Result<f64, StatsError> {
+        Ok(self.max)
+    }
+
+    fn merge(&mut self, other: &Self) {
+        self.push(other.max);
+    }
+}
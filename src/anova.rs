@@ -0,0 +1,358 @@
+// Copyright © 2019 Bader Alshaya
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Comparisons of several groups at once: the one-way
+//! analysis of variance, with Tukey's post-hoc comparisons
+//! of each pair of groups, and the Kruskal-Wallis test. The
+//! groups are given as a slice of samples, which may differ
+//! in size. A NaN value makes the whole result NaN.
+
+use crate::distributions::{ChiSquared, Distribution, F};
+use crate::rank::tie_sizes;
+use crate::select::compare;
+use crate::special::{studentized_range_quantile, studentized_range_sf};
+use crate::{mean, ranks, StatsError, TestResult};
+
+/// The result of a one-way analysis of variance.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Anova {
+    /// The F statistic: the mean square between the groups
+    /// over the mean square within them.
+    pub statistic: f64,
+    /// Degrees of freedom between the groups: one fewer than
+    /// the number of groups.
+    pub between_degrees_of_freedom: f64,
+    /// Degrees of freedom within the groups: the number of
+    /// values less the number of groups.
+    pub within_degrees_of_freedom: f64,
+    /// Probability of an F statistic at least this large if
+    /// the groups all had the same mean.
+    pub p_value: f64,
+    /// Eta squared, the proportion of the total sum of
+    /// squares that is between the groups.
+    pub eta_squared: f64,
+}
+
+impl Anova {
+    /// Result for input containing NaN.
+    const NAN: Anova = Anova {
+        statistic: f64::NAN,
+        between_degrees_of_freedom: f64::NAN,
+        within_degrees_of_freedom: f64::NAN,
+        p_value: f64::NAN,
+        eta_squared: f64::NAN,
+    };
+}
+
+/// Tukey's comparison of the means of two groups.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TukeyComparison {
+    /// Index of the first group.
+    pub first: usize,
+    /// Index of the second group, which is after the first.
+    pub second: usize,
+    /// The mean of the second group less the mean of the
+    /// first.
+    pub difference: f64,
+    /// Lower end of the simultaneous confidence interval for
+    /// the difference.
+    pub lower: f64,
+    /// Upper end of the simultaneous confidence interval for
+    /// the difference.
+    pub upper: f64,
+    /// P-value of the difference, adjusted for the number of
+    /// comparisons.
+    pub p_value: f64,
+}
+
+impl TukeyComparison {
+    /// Result for input containing NaN, but for the groups.
+    const NAN: TukeyComparison = TukeyComparison {
+        first: 0,
+        second: 0,
+        difference: f64::NAN,
+        lower: f64::NAN,
+        upper: f64::NAN,
+        p_value: f64::NAN,
+    };
+}
+
+/// The sizes and means of some groups, with their sums of
+/// squares.
+struct Groups {
+    /// Number of values in each group.
+    sizes: Vec<f64>,
+    /// Mean of each group.
+    means: Vec<f64>,
+    /// Sum of squared deviations of the group means from the
+    /// grand mean, weighted by the group sizes.
+    between: f64,
+    /// Sum of squared deviations of the values from their
+    /// group means.
+    within: f64,
+}
+
+impl Groups {
+    /// Degrees of freedom between the groups.
+    fn between_df(&self) -> f64 {
+        self.sizes.len() as f64 - 1.0
+    }
+
+    /// Degrees of freedom within the groups.
+    fn within_df(&self) -> f64 {
+        self.sizes.iter().sum::<f64>() - self.sizes.len() as f64
+    }
+}
+
+/// Check that there are at least two `groups`, none of them
+/// empty, with more values than groups in all. `Ok(false)`
+/// is for groups containing NaN.
+fn check_groups(groups: &[&[f64]]) -> Result<bool, StatsError> {
+    StatsError::check_len(groups.len(), 2)?;
+    if groups.iter().any(|group| group.is_empty()) {
+        return Err(StatsError::EmptyInput);
+    }
+    let n = groups.iter().map(|group| group.len()).sum();
+    StatsError::check_len(n, groups.len() + 1)?;
+    Ok(!groups.iter().any(|group| group.iter().any(|x| x.is_nan())))
+}
+
+/// The sums of squares of `groups`, or `None` if they
+/// contain NaN.
+fn sums_of_squares(groups: &[&[f64]]) -> Result<Option<Groups>, StatsError> {
+    if !check_groups(groups)? {
+        return Ok(None);
+    }
+    let sizes: Vec<f64> = groups.iter().map(|group| group.len() as f64).collect();
+    let means: Vec<f64> = groups.iter().map(|group| mean(group).unwrap()).collect();
+    let n: f64 = sizes.iter().sum();
+    let grand = sizes.iter().zip(&means).map(|(n, m)| n * m).sum::<f64>() / n;
+    let between = sizes
+        .iter()
+        .zip(&means)
+        .map(|(n, m)| n * (m - grand) * (m - grand))
+        .sum();
+    let within: f64 = groups
+        .iter()
+        .zip(&means)
+        .flat_map(|(group, m)| group.iter().map(move |x| (x - m) * (x - m)))
+        .sum();
+    if within == 0.0 {
+        return Err(StatsError::ZeroVariance);
+    }
+    Ok(Some(Groups {
+        sizes,
+        means,
+        between,
+        within,
+    }))
+}
+
+/// One-way analysis of variance of the hypothesis that all
+/// the `groups` come from normal distributions of the same
+/// mean, assuming that their variances are equal. The test
+/// is undefined for fewer than two groups, if a group is
+/// empty, unless there are more values than groups, or if
+/// the values of each group are all equal.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// // Warp breaks per loom at low, medium and high tension,
+/// // from Tippett (1950).
+/// let low = [26.0, 30.0, 54.0, 25.0, 70.0, 52.0, 51.0, 26.0, 67.0,
+///            27.0, 14.0, 29.0, 19.0, 29.0, 31.0, 41.0, 20.0, 44.0];
+/// let medium = [18.0, 21.0, 29.0, 17.0, 12.0, 18.0, 35.0, 30.0, 36.0,
+///               42.0, 26.0, 19.0, 16.0, 39.0, 28.0, 21.0, 39.0, 29.0];
+/// let high = [36.0, 21.0, 24.0, 18.0, 10.0, 43.0, 28.0, 15.0, 26.0,
+///             20.0, 21.0, 24.0, 17.0, 13.0, 15.0, 15.0, 16.0, 28.0];
+/// let anova = one_way_anova(&[&low, &medium, &high]).unwrap();
+/// assert!((anova.statistic - 7.206113880871162).abs() < 1e-12);
+/// assert_eq!(2.0, anova.between_degrees_of_freedom);
+/// assert_eq!(51.0, anova.within_degrees_of_freedom);
+/// assert!((anova.p_value - 0.001752816745852712).abs() < 1e-15);
+/// assert!((anova.eta_squared - 0.2203292603676099).abs() < 1e-15);
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, one_way_anova(&[&[1.0, 2.0]]));
+/// assert_eq!(None, one_way_anova(&[&[1.0], &[2.0]]));
+/// ```
+pub fn one_way_anova(groups: &[&[f64]]) -> Option<Anova> {
+    try_one_way_anova(groups).ok()
+}
+
+/// One-way analysis of variance, or why it is undefined.
+pub(crate) fn try_one_way_anova(groups: &[&[f64]]) -> Result<Anova, StatsError> {
+    let sums = match sums_of_squares(groups)? {
+        Some(sums) => sums,
+        None => return Ok(Anova::NAN),
+    };
+    let (df1, df2) = (sums.between_df(), sums.within_df());
+    let statistic = (sums.between / df1) / (sums.within / df2);
+    Ok(Anova {
+        statistic,
+        between_degrees_of_freedom: df1,
+        within_degrees_of_freedom: df2,
+        p_value: F::new(df1, df2).unwrap().sf(statistic),
+        eta_squared: sums.between / (sums.between + sums.within),
+    })
+}
+
+/// Tukey's honestly significant differences between the
+/// means of each pair of `groups`, following a one-way
+/// analysis of variance. Each difference has a p-value and
+/// a confidence interval at the given confidence `level`
+/// (0.95 for 95%), from the studentized range distribution,
+/// so that all the intervals hold together at that level.
+/// Groups of different sizes get the Tukey-Kramer intervals.
+/// The pairs are in the order (0, 1), (0, 2), ... (1, 2),
+/// and so on. The comparisons are undefined when the
+/// analysis of variance is, or unless `level` is between 0
+/// and 1.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// # let low = [26.0, 30.0, 54.0, 25.0, 70.0, 52.0, 51.0, 26.0, 67.0,
+/// #            27.0, 14.0, 29.0, 19.0, 29.0, 31.0, 41.0, 20.0, 44.0];
+/// # let medium = [18.0, 21.0, 29.0, 17.0, 12.0, 18.0, 35.0, 30.0, 36.0,
+/// #               42.0, 26.0, 19.0, 16.0, 39.0, 28.0, 21.0, 39.0, 29.0];
+/// # let high = [36.0, 21.0, 24.0, 18.0, 10.0, 43.0, 28.0, 15.0, 26.0,
+/// #             20.0, 21.0, 24.0, 17.0, 13.0, 15.0, 15.0, 16.0, 28.0];
+/// // The warp breaks at three tensions.
+/// let comparisons = tukey_hsd(&[&low, &medium, &high], 0.95).unwrap();
+/// let medium_low = comparisons[0];
+/// assert_eq!((0, 1), (medium_low.first, medium_low.second));
+/// assert!((medium_low.difference - -10.0).abs() < 1e-12);
+/// assert!((medium_low.lower - -19.55982444).abs() < 1e-8);
+/// assert!((medium_low.upper - -0.44017556).abs() < 1e-8);
+/// assert!((medium_low.p_value - 0.03845976807).abs() < 1e-10);
+/// let high_medium = comparisons[2];
+/// assert!((high_medium.p_value - 0.4630830971).abs() < 1e-9);
+/// ```
+pub fn tukey_hsd(groups: &[&[f64]], level: f64) -> Option<Vec<TukeyComparison>> {
+    try_tukey_hsd(groups, level).ok()
+}
+
+/// Tukey's honestly significant differences, or why they
+/// are undefined.
+pub(crate) fn try_tukey_hsd(
+    groups: &[&[f64]],
+    level: f64,
+) -> Result<Vec<TukeyComparison>, StatsError> {
+    if !(level > 0.0 && level < 1.0) {
+        return Err(StatsError::InvalidParameter(
+            "confidence level must be between 0 and 1",
+        ));
+    }
+    let k = groups.len();
+    let pairs = (0..k).flat_map(|i| (i + 1..k).map(move |j| (i, j)));
+    let sums = match sums_of_squares(groups)? {
+        Some(sums) => sums,
+        None => {
+            return Ok(pairs
+                .map(|(first, second)| TukeyComparison {
+                    first,
+                    second,
+                    ..TukeyComparison::NAN
+                })
+                .collect())
+        }
+    };
+    let df = sums.within_df();
+    let mean_square = sums.within / df;
+    let critical = studentized_range_quantile(1.0 - level, k as f64, df);
+    Ok(pairs
+        .map(|(i, j)| {
+            let difference = sums.means[j] - sums.means[i];
+            let standard_error =
+                (mean_square / 2.0 * (1.0 / sums.sizes[i] + 1.0 / sums.sizes[j])).sqrt();
+            let margin = critical * standard_error;
+            TukeyComparison {
+                first: i,
+                second: j,
+                difference,
+                lower: difference - margin,
+                upper: difference + margin,
+                p_value: studentized_range_sf(difference.abs() / standard_error, k as f64, df),
+            }
+        })
+        .collect())
+}
+
+/// Kruskal-Wallis test of the hypothesis that all the
+/// `groups` come from the same distribution, against the
+/// alternative that some tend to have larger values than
+/// others: the rank-based counterpart of the one-way
+/// analysis of variance. The statistic H is computed from
+/// the ranks of all the values together, corrected for ties,
+/// and its p-value is from the chi-squared distribution
+/// with one degree of freedom fewer than the number of
+/// groups. The test is undefined for fewer than two groups,
+/// if a group is empty, unless there are more values than
+/// groups, or if the values are all equal.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// # let low = [26.0, 30.0, 54.0, 25.0, 70.0, 52.0, 51.0, 26.0, 67.0,
+/// #            27.0, 14.0, 29.0, 19.0, 29.0, 31.0, 41.0, 20.0, 44.0];
+/// # let medium = [18.0, 21.0, 29.0, 17.0, 12.0, 18.0, 35.0, 30.0, 36.0,
+/// #               42.0, 26.0, 19.0, 16.0, 39.0, 28.0, 21.0, 39.0, 29.0];
+/// # let high = [36.0, 21.0, 24.0, 18.0, 10.0, 43.0, 28.0, 15.0, 26.0,
+/// #             20.0, 21.0, 24.0, 17.0, 13.0, 15.0, 15.0, 16.0, 28.0];
+/// // The warp breaks at three tensions.
+/// let test = kruskal_wallis(&[&low, &medium, &high]).unwrap();
+/// assert!((test.statistic - 10.80926527061719).abs() < 1e-12);
+/// assert!((test.p_value - 0.004495705661380477).abs() < 1e-15);
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, kruskal_wallis(&[&[1.0, 1.0], &[1.0]]));
+/// ```
+pub fn kruskal_wallis(groups: &[&[f64]]) -> Option<TestResult> {
+    try_kruskal_wallis(groups).ok()
+}
+
+/// Kruskal-Wallis test, or why it is undefined.
+pub(crate) fn try_kruskal_wallis(groups: &[&[f64]]) -> Result<TestResult, StatsError> {
+    if !check_groups(groups)? {
+        return Ok(TestResult::NAN);
+    }
+    let all: Vec<f64> = groups
+        .iter()
+        .flat_map(|group| group.iter().copied())
+        .collect();
+    let n = all.len() as f64;
+    let ranks = ranks(&all);
+    let mut start = 0;
+    let mut sum = 0.0;
+    for group in groups {
+        let rank_sum: f64 = ranks[start..start + group.len()].iter().sum();
+        sum += rank_sum * rank_sum / group.len() as f64;
+        start += group.len();
+    }
+    let mut sorted = all;
+    sorted.sort_unstable_by(compare);
+    let ties: f64 = tie_sizes(&sorted)
+        .map(|t| {
+            let t = t as f64;
+            t * t * t - t
+        })
+        .sum();
+    let correction = 1.0 - ties / (n * n * n - n);
+    if correction == 0.0 {
+        return Err(StatsError::ZeroVariance);
+    }
+    let statistic = (12.0 / (n * (n + 1.0)) * sum - 3.0 * (n + 1.0)) / correction;
+    let p_value = ChiSquared::new(groups.len() as f64 - 1.0)
+        .unwrap()
+        .sf(statistic);
+    Ok(TestResult { statistic, p_value })
+}
@@ -0,0 +1,1158 @@
+// Copyright © 2019 Bader Alshaya
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Versions of the statistics that say why a result is
+//! undefined. Each function here computes the same value as
+//! the function of the same name at the crate root, but
+//! returns a [`StatsError`] where that one returns `None`.
+//! These versions also refuse input containing NaN, with
+//! [`StatsError::ContainsNan`], rather than propagating it;
+//! apply a [`NanPolicy`](crate::NanPolicy) first to treat NaN
+//! otherwise.
+
+use crate::distributions::Distribution;
+use crate::{
+    Accumulator, Anova, Correlation, CurveFit, MedianPolicy, ModePolicy, QuantileMethod,
+    Regression, StatsError, Summary, TTest, TestResult, TukeyComparison, WeightKind,
+};
+
+/// Type of checked statistics function.
+pub type TryStatFn = fn(&[f64]) -> Result<f64, StatsError>;
+
+/// Push all of `nums` into `acc`, which gives the reason
+/// for any undefined result, and return its result.
+fn accumulate<A: Accumulator>(mut acc: A, nums: &[f64]) -> Result<f64, StatsError> {
+    StatsError::check(nums, 0)?;
+    acc.push_all(nums);
+    acc.try_result()
+}
+
+/// Check a probability for a quantile.
+fn check_probability(p: f64) -> Result<(), StatsError> {
+    if (0.0..=1.0).contains(&p) {
+        Ok(())
+    } else {
+        Err(StatsError::InvalidParameter(
+            "probability must be between 0 and 1",
+        ))
+    }
+}
+
+/// Check a trimming fraction.
+fn check_fraction(fraction: f64) -> Result<(), StatsError> {
+    if (0.0..0.5).contains(&fraction) {
+        Ok(())
+    } else {
+        Err(StatsError::InvalidParameter(
+            "fraction must be at least 0 and below 0.5",
+        ))
+    }
+}
+
+/// Arithmetic mean; see [`crate::mean`]. The mean of an
+/// empty list is 0.0.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Ok(0.0), checked::mean(&[]));
+/// assert_eq!(Err(StatsError::ContainsNan), checked::mean(&[1.0, f64::NAN]));
+/// ```
+pub fn mean(nums: &[f64]) -> Result<f64, StatsError> {
+    accumulate(crate::RunningMean::default(), nums)
+}
+
+/// Geometric mean; see [`crate::geometric_mean`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Err(StatsError::NegativeValue), checked::geometric_mean(&[-2.0, 2.0]));
+/// ```
+pub fn geometric_mean(nums: &[f64]) -> Result<f64, StatsError> {
+    accumulate(crate::RunningGeometricMean::default(), nums)
+}
+
+/// Harmonic mean; see [`crate::harmonic_mean`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Err(StatsError::EmptyInput), checked::harmonic_mean(&[]));
+/// ```
+pub fn harmonic_mean(nums: &[f64]) -> Result<f64, StatsError> {
+    accumulate(crate::RunningHarmonicMean::default(), nums)
+}
+
+/// Power mean; see [`crate::power_mean`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert!(matches!(
+///     checked::power_mean(&[1.0], f64::NAN),
+///     Err(StatsError::InvalidParameter(_))
+/// ));
+/// ```
+pub fn power_mean(nums: &[f64], p: f64) -> Result<f64, StatsError> {
+    StatsError::check(nums, 0)?;
+    crate::means::try_power_mean(nums, p)
+}
+
+/// Population standard deviation; see [`crate::stddev`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Err(StatsError::EmptyInput), checked::stddev(&[]));
+/// assert_eq!(Ok(0.0), checked::stddev(&[1.0]));
+/// ```
+pub fn stddev(nums: &[f64]) -> Result<f64, StatsError> {
+    accumulate(crate::RunningStddev::default(), nums)
+}
+
+/// Population variance; see [`crate::variance`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Err(StatsError::EmptyInput), checked::variance(&[]));
+/// ```
+pub fn variance(nums: &[f64]) -> Result<f64, StatsError> {
+    accumulate(crate::RunningVariance::default(), nums)
+}
+
+/// Sample variance; see [`crate::sample_variance`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(
+///     Err(StatsError::NotEnoughSamples { needed: 2, got: 1 }),
+///     checked::sample_variance(&[1.0])
+/// );
+/// ```
+pub fn sample_variance(nums: &[f64]) -> Result<f64, StatsError> {
+    accumulate(crate::RunningVariance::sample(), nums)
+}
+
+/// Sample standard deviation; see [`crate::sample_stddev`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Ok(2.0), checked::sample_stddev(&[1.0, 3.0, 5.0]));
+/// ```
+pub fn sample_stddev(nums: &[f64]) -> Result<f64, StatsError> {
+    accumulate(crate::RunningStddev::sample(), nums)
+}
+
+/// The `k`-th raw moment; see [`crate::moment`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Err(StatsError::EmptyInput), checked::moment(&[], 2));
+/// ```
+pub fn moment(nums: &[f64], k: u32) -> Result<f64, StatsError> {
+    StatsError::check(nums, 1)?;
+    Ok(crate::moment(nums, k).unwrap())
+}
+
+/// The `k`-th central moment; see [`crate::central_moment`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Ok(1.0), checked::central_moment(&[-1.0, 1.0], 2));
+/// ```
+pub fn central_moment(nums: &[f64], k: u32) -> Result<f64, StatsError> {
+    StatsError::check(nums, 1)?;
+    Ok(crate::central_moment(nums, k).unwrap())
+}
+
+/// Population skewness; see [`crate::skewness`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Err(StatsError::ZeroVariance), checked::skewness(&[5.0, 5.0]));
+/// ```
+pub fn skewness(nums: &[f64]) -> Result<f64, StatsError> {
+    accumulate(crate::RunningSkewness::default(), nums)
+}
+
+/// Sample skewness; see [`crate::sample_skewness`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(
+///     Err(StatsError::NotEnoughSamples { needed: 3, got: 2 }),
+///     checked::sample_skewness(&[1.0, 2.0])
+/// );
+/// ```
+pub fn sample_skewness(nums: &[f64]) -> Result<f64, StatsError> {
+    accumulate(crate::RunningSkewness::sample(), nums)
+}
+
+/// Population excess kurtosis; see [`crate::kurtosis`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Ok(-2.0), checked::kurtosis(&[-1.0, 1.0]));
+/// ```
+pub fn kurtosis(nums: &[f64]) -> Result<f64, StatsError> {
+    accumulate(crate::RunningKurtosis::default(), nums)
+}
+
+/// Sample excess kurtosis; see [`crate::sample_kurtosis`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Err(StatsError::EmptyInput), checked::sample_kurtosis(&[]));
+/// ```
+pub fn sample_kurtosis(nums: &[f64]) -> Result<f64, StatsError> {
+    accumulate(crate::RunningKurtosis::sample(), nums)
+}
+
+/// Median, taking the lower middle value; see
+/// [`crate::median`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Err(StatsError::EmptyInput), checked::median(&[]));
+/// ```
+pub fn median(nums: &[f64]) -> Result<f64, StatsError> {
+    median_with(nums, MedianPolicy::Lower)
+}
+
+/// Median with a choice of tie-break; see
+/// [`crate::median_with`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Ok(2.5), checked::median_with(&[1.0, 2.0, 3.0, 4.0], MedianPolicy::Midpoint));
+/// ```
+pub fn median_with(nums: &[f64], policy: MedianPolicy) -> Result<f64, StatsError> {
+    StatsError::check(nums, 1)?;
+    Ok(crate::median_with(nums, policy).unwrap())
+}
+
+/// Median found in place; see [`crate::median_in_place`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Ok(3.0), checked::median_in_place(&mut [3.0, 1.0, 4.0], MedianPolicy::Lower));
+/// ```
+pub fn median_in_place(nums: &mut [f64], policy: MedianPolicy) -> Result<f64, StatsError> {
+    StatsError::check(nums, 1)?;
+    Ok(crate::median_in_place(nums, policy).unwrap())
+}
+
+/// Trimmed mean; see [`crate::trimmed_mean`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert!(matches!(
+///     checked::trimmed_mean(&[1.0], 0.5),
+///     Err(StatsError::InvalidParameter(_))
+/// ));
+/// ```
+pub fn trimmed_mean(nums: &[f64], fraction: f64) -> Result<f64, StatsError> {
+    check_fraction(fraction)?;
+    StatsError::check(nums, 1)?;
+    Ok(crate::trimmed_mean(nums, fraction).unwrap())
+}
+
+/// Winsorized mean; see [`crate::winsorized_mean`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Err(StatsError::EmptyInput), checked::winsorized_mean(&[], 0.1));
+/// ```
+pub fn winsorized_mean(nums: &[f64], fraction: f64) -> Result<f64, StatsError> {
+    check_fraction(fraction)?;
+    StatsError::check(nums, 1)?;
+    Ok(crate::winsorized_mean(nums, fraction).unwrap())
+}
+
+/// Median absolute deviation; see [`crate::mad`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Err(StatsError::EmptyInput), checked::mad(&[]));
+/// ```
+pub fn mad(nums: &[f64]) -> Result<f64, StatsError> {
+    StatsError::check(nums, 1)?;
+    Ok(crate::mad(nums).unwrap())
+}
+
+/// Normal-consistent median absolute deviation; see
+/// [`crate::normal_mad`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Ok(0.0), checked::normal_mad(&[3.0]));
+/// ```
+pub fn normal_mad(nums: &[f64]) -> Result<f64, StatsError> {
+    StatsError::check(nums, 1)?;
+    Ok(crate::normal_mad(nums).unwrap())
+}
+
+/// Interquartile range; see [`crate::iqr`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Err(StatsError::ContainsNan), checked::iqr(&[f64::NAN], QuantileMethod::Linear));
+/// ```
+pub fn iqr(nums: &[f64], method: QuantileMethod) -> Result<f64, StatsError> {
+    StatsError::check(nums, 1)?;
+    Ok(crate::iqr(nums, method).unwrap())
+}
+
+/// Hodges–Lehmann estimator; see [`crate::hodges_lehmann`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Ok(2.0), checked::hodges_lehmann(&[1.0, 2.0, 3.0]));
+/// ```
+pub fn hodges_lehmann(nums: &[f64]) -> Result<f64, StatsError> {
+    StatsError::check(nums, 1)?;
+    Ok(crate::hodges_lehmann(nums).unwrap())
+}
+
+/// Huber M-estimator of location; see
+/// [`crate::huber_location`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert!(matches!(
+///     checked::huber_location(&[1.0], -1.0),
+///     Err(StatsError::InvalidParameter(_))
+/// ));
+/// ```
+pub fn huber_location(nums: &[f64], k: f64) -> Result<f64, StatsError> {
+    if k.is_nan() || k <= 0.0 {
+        return Err(StatsError::InvalidParameter(
+            "tuning constant must be positive",
+        ));
+    }
+    StatsError::check(nums, 1)?;
+    Ok(crate::huber_location(nums, k).unwrap())
+}
+
+/// Mode, taking the smallest of several most common values;
+/// see [`crate::mode`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Err(StatsError::EmptyInput), checked::mode(&[]));
+/// ```
+pub fn mode(nums: &[f64]) -> Result<f64, StatsError> {
+    mode_with(nums, ModePolicy::Smallest)
+}
+
+/// Mode with a choice of tie-break; see
+/// [`crate::mode_with`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(
+///     Err(StatsError::Multimodal),
+///     checked::mode_with(&[1.0, 2.0], ModePolicy::Unique)
+/// );
+/// ```
+pub fn mode_with(nums: &[f64], policy: ModePolicy) -> Result<f64, StatsError> {
+    StatsError::check(nums, 1)?;
+    crate::mode_with(nums, policy).ok_or(StatsError::Multimodal)
+}
+
+/// L1 norm; see [`crate::l1`]. The L1 norm of an empty list
+/// is 0.0.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Ok(7.0), checked::l1(&[3.0, 4.0]));
+/// ```
+pub fn l1(nums: &[f64]) -> Result<f64, StatsError> {
+    accumulate(crate::RunningL1::default(), nums)
+}
+
+/// L2 norm; see [`crate::l2`]. The L2 norm of an empty list
+/// is 0.0.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Ok(5.0), checked::l2(&[3.0, 4.0]));
+/// ```
+pub fn l2(nums: &[f64]) -> Result<f64, StatsError> {
+    accumulate(crate::RunningL2::default(), nums)
+}
+
+/// L∞ norm; see [`crate::linf`]. The L∞ norm of an empty
+/// list is 0.0.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Err(StatsError::ContainsNan), checked::linf(&[f64::NAN]));
+/// ```
+pub fn linf(nums: &[f64]) -> Result<f64, StatsError> {
+    accumulate(crate::RunningLinf::default(), nums)
+}
+
+/// Lp norm; see [`crate::lp`]. The Lp norm of an empty list
+/// is 0.0.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert!(matches!(checked::lp(&[1.0], 0.5), Err(StatsError::InvalidParameter(_))));
+/// ```
+pub fn lp(nums: &[f64], p: f64) -> Result<f64, StatsError> {
+    if p.is_nan() || p < 1.0 {
+        return Err(StatsError::InvalidParameter("p must be at least 1"));
+    }
+    StatsError::check(nums, 0)?;
+    Ok(crate::lp(nums, p).unwrap())
+}
+
+/// Dot product; see [`crate::dot`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Err(StatsError::LengthMismatch), checked::dot(&[1.0], &[]));
+/// ```
+pub fn dot(xs: &[f64], ys: &[f64]) -> Result<f64, StatsError> {
+    StatsError::check_pairs(xs, ys, 0)?;
+    Ok(crate::dot(xs, ys).unwrap())
+}
+
+/// Euclidean distance; see [`crate::euclidean_distance`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Ok(5.0), checked::euclidean_distance(&[0.0, 0.0], &[3.0, 4.0]));
+/// ```
+pub fn euclidean_distance(xs: &[f64], ys: &[f64]) -> Result<f64, StatsError> {
+    StatsError::check_pairs(xs, ys, 0)?;
+    Ok(crate::euclidean_distance(xs, ys).unwrap())
+}
+
+/// Manhattan distance; see [`crate::manhattan_distance`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Ok(7.0), checked::manhattan_distance(&[0.0, 0.0], &[3.0, 4.0]));
+/// ```
+pub fn manhattan_distance(xs: &[f64], ys: &[f64]) -> Result<f64, StatsError> {
+    StatsError::check_pairs(xs, ys, 0)?;
+    Ok(crate::manhattan_distance(xs, ys).unwrap())
+}
+
+/// Cosine distance; see [`crate::cosine_distance`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Err(StatsError::ZeroNorm), checked::cosine_distance(&[0.0], &[1.0]));
+/// ```
+pub fn cosine_distance(xs: &[f64], ys: &[f64]) -> Result<f64, StatsError> {
+    StatsError::check_pairs(xs, ys, 0)?;
+    crate::cosine_distance(xs, ys).ok_or(StatsError::ZeroNorm)
+}
+
+/// Population covariance; see [`crate::covariance`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Err(StatsError::EmptyInput), checked::covariance(&[], &[]));
+/// ```
+pub fn covariance(xs: &[f64], ys: &[f64]) -> Result<f64, StatsError> {
+    StatsError::check_pairs(xs, ys, 1)?;
+    Ok(crate::covariance(xs, ys).unwrap())
+}
+
+/// Sample covariance; see [`crate::sample_covariance`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Ok(2.0), checked::sample_covariance(&[1.0, 2.0, 3.0], &[2.0, 4.0, 6.0]));
+/// ```
+pub fn sample_covariance(xs: &[f64], ys: &[f64]) -> Result<f64, StatsError> {
+    StatsError::check_pairs(xs, ys, 2)?;
+    Ok(crate::sample_covariance(xs, ys).unwrap())
+}
+
+/// Pearson correlation coefficient; see [`crate::pearson`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Err(StatsError::ZeroVariance), checked::pearson(&[1.0, 2.0], &[5.0, 5.0]));
+/// ```
+pub fn pearson(xs: &[f64], ys: &[f64]) -> Result<Correlation, StatsError> {
+    StatsError::check_pairs(xs, ys, 2)?;
+    crate::correlation::try_pearson(xs, ys)
+}
+
+/// Spearman rank correlation coefficient; see
+/// [`crate::spearman`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Err(StatsError::ContainsNan), checked::spearman(&[1.0, 2.0], &[f64::NAN, 1.0]));
+/// ```
+pub fn spearman(xs: &[f64], ys: &[f64]) -> Result<Correlation, StatsError> {
+    StatsError::check_pairs(xs, ys, 2)?;
+    crate::correlation::try_spearman(xs, ys)
+}
+
+/// Kendall rank correlation coefficient tau-b; see
+/// [`crate::kendall_tau_b`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(
+///     Err(StatsError::NotEnoughSamples { needed: 2, got: 1 }),
+///     checked::kendall_tau_b(&[1.0], &[1.0])
+/// );
+/// ```
+pub fn kendall_tau_b(xs: &[f64], ys: &[f64]) -> Result<Correlation, StatsError> {
+    StatsError::check_pairs(xs, ys, 2)?;
+    crate::correlation::try_kendall_tau_b(xs, ys)
+}
+
+/// Simple linear regression; see
+/// [`crate::simple_regression`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(
+///     Err(StatsError::ZeroVariance),
+///     checked::simple_regression(&[1.0, 1.0, 1.0], &[1.0, 2.0, 3.0]).map(|fit| fit.slope())
+/// );
+/// ```
+pub fn simple_regression(xs: &[f64], ys: &[f64]) -> Result<Regression, StatsError> {
+    multiple_regression(&[xs], ys)
+}
+
+/// Multiple linear regression; see
+/// [`crate::multiple_regression`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// let x1 = [1.0, 2.0, 3.0, 4.0];
+/// let x2 = [2.0, 4.0, 6.0, 8.0];
+/// assert_eq!(
+///     Err(StatsError::Collinear),
+///     checked::multiple_regression(&[&x1, &x2], &[1.0, 3.0, 2.0, 4.0])
+/// );
+/// ```
+pub fn multiple_regression(predictors: &[&[f64]], ys: &[f64]) -> Result<Regression, StatsError> {
+    for xs in predictors {
+        StatsError::check_pairs(xs, ys, 0)?;
+    }
+    StatsError::check(ys, predictors.len() + 2)?;
+    crate::regression::try_multiple_regression(predictors, ys)
+}
+
+/// Polynomial fit; see [`crate::polynomial_fit`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(
+///     Err(StatsError::NotEnoughSamples { needed: 4, got: 3 }),
+///     checked::polynomial_fit(&[0.0, 1.0, 2.0], &[1.0, 2.0, 5.0], 2)
+/// );
+/// ```
+pub fn polynomial_fit(xs: &[f64], ys: &[f64], degree: usize) -> Result<CurveFit, StatsError> {
+    StatsError::check_pairs(xs, ys, degree + 2)?;
+    crate::curve::try_polynomial_fit(xs, ys, degree)
+}
+
+/// Exponential fit; see [`crate::exponential_fit`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(
+///     Err(StatsError::NonPositiveValue),
+///     checked::exponential_fit(&[0.0, 1.0, 2.0], &[1.0, 0.0, 1.0])
+/// );
+/// ```
+pub fn exponential_fit(xs: &[f64], ys: &[f64]) -> Result<CurveFit, StatsError> {
+    StatsError::check_pairs(xs, ys, 3)?;
+    crate::curve::try_exponential_fit(xs, ys)
+}
+
+/// Power-law fit; see [`crate::power_law_fit`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(
+///     Err(StatsError::NonPositiveValue),
+///     checked::power_law_fit(&[0.0, 1.0, 2.0], &[1.0, 2.0, 3.0])
+/// );
+/// ```
+pub fn power_law_fit(xs: &[f64], ys: &[f64]) -> Result<CurveFit, StatsError> {
+    StatsError::check_pairs(xs, ys, 3)?;
+    crate::curve::try_power_law_fit(xs, ys)
+}
+
+/// Mann-Whitney U test; see [`crate::mann_whitney_u`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Err(StatsError::ZeroVariance), checked::mann_whitney_u(&[1.0; 60], &[1.0]));
+/// ```
+pub fn mann_whitney_u(xs: &[f64], ys: &[f64]) -> Result<TestResult, StatsError> {
+    StatsError::check(xs, 1)?;
+    StatsError::check(ys, 1)?;
+    crate::nonparametric::try_mann_whitney_u(xs, ys)
+}
+
+/// Wilcoxon signed-rank test; see
+/// [`crate::wilcoxon_signed_rank`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(
+///     Err(StatsError::LengthMismatch),
+///     checked::wilcoxon_signed_rank(&[1.0, 2.0], &[1.0])
+/// );
+/// ```
+pub fn wilcoxon_signed_rank(xs: &[f64], ys: &[f64]) -> Result<TestResult, StatsError> {
+    StatsError::check_pairs(xs, ys, 1)?;
+    crate::nonparametric::try_wilcoxon_signed_rank(xs, ys)
+}
+
+/// One-sample Kolmogorov-Smirnov test; see
+/// [`crate::kolmogorov_smirnov`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// # use stats::distributions::Normal;
+/// assert_eq!(
+///     Err(StatsError::EmptyInput),
+///     checked::kolmogorov_smirnov(&[], &Normal::standard())
+/// );
+/// ```
+pub fn kolmogorov_smirnov(nums: &[f64], dist: &dyn Distribution) -> Result<TestResult, StatsError> {
+    StatsError::check(nums, 1)?;
+    crate::nonparametric::try_kolmogorov_smirnov(nums, dist)
+}
+
+/// Two-sample Kolmogorov-Smirnov test; see
+/// [`crate::kolmogorov_smirnov_two_sample`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(
+///     Err(StatsError::ContainsNan),
+///     checked::kolmogorov_smirnov_two_sample(&[1.0], &[f64::NAN])
+/// );
+/// ```
+pub fn kolmogorov_smirnov_two_sample(xs: &[f64], ys: &[f64]) -> Result<TestResult, StatsError> {
+    StatsError::check(xs, 1)?;
+    StatsError::check(ys, 1)?;
+    crate::nonparametric::try_kolmogorov_smirnov_two_sample(xs, ys)
+}
+
+/// Shapiro-Wilk test of normality; see
+/// [`crate::shapiro_wilk`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(
+///     Err(StatsError::NotEnoughSamples { needed: 3, got: 2 }),
+///     checked::shapiro_wilk(&[1.0, 2.0])
+/// );
+/// ```
+pub fn shapiro_wilk(nums: &[f64]) -> Result<TestResult, StatsError> {
+    StatsError::check(nums, 3)?;
+    crate::normality::try_shapiro_wilk(nums)
+}
+
+/// Anderson-Darling test of normality; see
+/// [`crate::anderson_darling`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Err(StatsError::ZeroVariance), checked::anderson_darling(&[2.0; 8]));
+/// ```
+pub fn anderson_darling(nums: &[f64]) -> Result<TestResult, StatsError> {
+    StatsError::check(nums, 8)?;
+    crate::normality::try_anderson_darling(nums)
+}
+
+/// Jarque-Bera test of normality; see
+/// [`crate::jarque_bera`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(
+///     Err(StatsError::ContainsNan),
+///     checked::jarque_bera(&[1.0, f64::NAN, 3.0])
+/// );
+/// ```
+pub fn jarque_bera(nums: &[f64]) -> Result<TestResult, StatsError> {
+    StatsError::check(nums, 2)?;
+    crate::normality::try_jarque_bera(nums)
+}
+
+/// Check that `groups` contain no NaN.
+fn check_groups(groups: &[&[f64]]) -> Result<(), StatsError> {
+    groups
+        .iter()
+        .try_for_each(|group| StatsError::check(group, 0))
+}
+
+/// One-way analysis of variance; see
+/// [`crate::one_way_anova`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(
+///     Err(StatsError::ZeroVariance),
+///     checked::one_way_anova(&[&[1.0, 1.0], &[2.0, 2.0]])
+/// );
+/// ```
+pub fn one_way_anova(groups: &[&[f64]]) -> Result<Anova, StatsError> {
+    check_groups(groups)?;
+    crate::anova::try_one_way_anova(groups)
+}
+
+/// Tukey's honestly significant differences; see
+/// [`crate::tukey_hsd`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(
+///     Err(StatsError::EmptyInput),
+///     checked::tukey_hsd(&[&[1.0, 2.0], &[]], 0.95)
+/// );
+/// ```
+pub fn tukey_hsd(groups: &[&[f64]], level: f64) -> Result<Vec<TukeyComparison>, StatsError> {
+    check_groups(groups)?;
+    crate::anova::try_tukey_hsd(groups, level)
+}
+
+/// Kruskal-Wallis test; see [`crate::kruskal_wallis`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(
+///     Err(StatsError::ContainsNan),
+///     checked::kruskal_wallis(&[&[1.0, 2.0], &[f64::NAN]])
+/// );
+/// ```
+pub fn kruskal_wallis(groups: &[&[f64]]) -> Result<TestResult, StatsError> {
+    check_groups(groups)?;
+    crate::anova::try_kruskal_wallis(groups)
+}
+
+/// Chi-squared goodness-of-fit test; see
+/// [`crate::chi_squared_goodness_of_fit`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(
+///     Err(StatsError::NegativeValue),
+///     checked::chi_squared_goodness_of_fit(&[-1.0, 2.0], &[1.0, 1.0])
+/// );
+/// ```
+pub fn chi_squared_goodness_of_fit(
+    observed: &[f64],
+    proportions: &[f64],
+) -> Result<TestResult, StatsError> {
+    StatsError::check(observed, 0)?;
+    crate::contingency::try_chi_squared_goodness_of_fit(observed, proportions)
+}
+
+/// Check that a contingency `table` contains no NaN.
+fn check_table(table: &[&[f64]]) -> Result<(), StatsError> {
+    table.iter().try_for_each(|row| StatsError::check(row, 0))
+}
+
+/// Chi-squared test of independence; see
+/// [`crate::chi_squared_independence`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(
+///     Err(StatsError::EmptyCategory),
+///     checked::chi_squared_independence(&[&[1.0, 0.0], &[2.0, 0.0]])
+/// );
+/// ```
+pub fn chi_squared_independence(table: &[&[f64]]) -> Result<TestResult, StatsError> {
+    check_table(table)?;
+    crate::contingency::try_chi_squared_independence(table)
+}
+
+/// Cramér's V; see [`crate::cramers_v`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(
+///     Err(StatsError::LengthMismatch),
+///     checked::cramers_v(&[&[1.0, 2.0], &[3.0]])
+/// );
+/// ```
+pub fn cramers_v(table: &[&[f64]]) -> Result<f64, StatsError> {
+    check_table(table)?;
+    crate::contingency::try_cramers_v(table)
+}
+
+/// Fisher's exact test; see [`crate::fisher_exact`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(
+///     Err(StatsError::ContainsNan),
+///     checked::fisher_exact(&[&[1.0, f64::NAN], &[3.0, 4.0]])
+/// );
+/// ```
+pub fn fisher_exact(table: &[&[f64]]) -> Result<TestResult, StatsError> {
+    check_table(table)?;
+    crate::contingency::try_fisher_exact(table)
+}
+
+/// The `k`-th smallest value, found in place; see
+/// [`crate::select_in_place`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(
+///     Err(StatsError::NotEnoughSamples { needed: 3, got: 2 }),
+///     checked::select_in_place(&mut [1.0, 2.0], 2)
+/// );
+/// ```
+pub fn select_in_place(nums: &mut [f64], k: usize) -> Result<f64, StatsError> {
+    StatsError::check(nums, k + 1)?;
+    Ok(crate::select_in_place(nums, k).unwrap())
+}
+
+/// The `k`-th smallest value; see [`crate::order_statistic`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Ok(-1.0), checked::order_statistic(&[0.0, -1.0], 0));
+/// ```
+pub fn order_statistic(nums: &[f64], k: usize) -> Result<f64, StatsError> {
+    StatsError::check(nums, k + 1)?;
+    Ok(crate::order_statistic(nums, k).unwrap())
+}
+
+/// Quantile of sorted values; see [`crate::quantile_sorted`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert!(matches!(
+///     checked::quantile_sorted(&[1.0], 1.5, QuantileMethod::Linear),
+///     Err(StatsError::InvalidParameter(_))
+/// ));
+/// ```
+pub fn quantile_sorted(sorted: &[f64], p: f64, method: QuantileMethod) -> Result<f64, StatsError> {
+    check_probability(p)?;
+    StatsError::check(sorted, 1)?;
+    Ok(crate::quantile_sorted(sorted, p, method).unwrap())
+}
+
+/// Quantile; see [`crate::quantile`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Err(StatsError::EmptyInput), checked::quantile(&[], 0.5, QuantileMethod::Linear));
+/// ```
+pub fn quantile(nums: &[f64], p: f64, method: QuantileMethod) -> Result<f64, StatsError> {
+    check_probability(p)?;
+    StatsError::check(nums, 1)?;
+    Ok(crate::quantile(nums, p, method).unwrap())
+}
+
+/// Several quantiles; see [`crate::quantiles`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// let nums = [1.0, 2.0, 3.0];
+/// assert_eq!(Ok(vec![1.5, 2.5]), checked::quantiles(&nums, &[0.25, 0.75], QuantileMethod::Linear));
+/// ```
+pub fn quantiles(nums: &[f64], ps: &[f64], method: QuantileMethod) -> Result<Vec<f64>, StatsError> {
+    for &p in ps {
+        check_probability(p)?;
+    }
+    StatsError::check(nums, 1)?;
+    Ok(crate::quantiles(nums, ps, method).unwrap())
+}
+
+/// Summary statistics; see [`crate::summary`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Err(StatsError::EmptyInput), checked::summary(&[]));
+/// ```
+pub fn summary(nums: &[f64]) -> Result<Summary, StatsError> {
+    StatsError::check(nums, 1)?;
+    Ok(crate::summary(nums).unwrap())
+}
+
+/// One-sample t-test; see [`crate::one_sample_t_test`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(
+///     Err(StatsError::NotEnoughSamples { needed: 2, got: 1 }),
+///     checked::one_sample_t_test(&[1.0], 0.0)
+/// );
+/// ```
+pub fn one_sample_t_test(nums: &[f64], mu: f64) -> Result<TTest, StatsError> {
+    StatsError::check(nums, 2)?;
+    crate::ttest::try_one_sample_t_test(nums, mu)
+}
+
+/// Paired t-test; see [`crate::paired_t_test`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(
+///     Err(StatsError::ZeroVariance),
+///     checked::paired_t_test(&[2.0, 3.0], &[1.0, 2.0])
+/// );
+/// ```
+pub fn paired_t_test(xs: &[f64], ys: &[f64]) -> Result<TTest, StatsError> {
+    StatsError::check_pairs(xs, ys, 2)?;
+    crate::ttest::try_paired_t_test(xs, ys)
+}
+
+/// Welch's t-test; see [`crate::welch_t_test`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(
+///     Err(StatsError::ContainsNan),
+///     checked::welch_t_test(&[1.0, 2.0], &[f64::NAN, 1.0])
+/// );
+/// ```
+pub fn welch_t_test(xs: &[f64], ys: &[f64]) -> Result<TTest, StatsError> {
+    StatsError::check(xs, 2)?;
+    StatsError::check(ys, 2)?;
+    crate::ttest::try_welch_t_test(xs, ys)
+}
+
+/// Weighted mean; see [`crate::weighted_mean`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Err(StatsError::NegativeValue), checked::weighted_mean(&[1.0], &[-1.0]));
+/// ```
+pub fn weighted_mean(nums: &[f64], weights: &[f64]) -> Result<f64, StatsError> {
+    StatsError::check_pairs(nums, weights, 0)?;
+    crate::weighted::try_weighted_mean(nums, weights)
+}
+
+/// Weighted population variance; see
+/// [`crate::weighted_variance`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Err(StatsError::EmptyInput), checked::weighted_variance(&[], &[]));
+/// ```
+pub fn weighted_variance(nums: &[f64], weights: &[f64]) -> Result<f64, StatsError> {
+    StatsError::check_pairs(nums, weights, 0)?;
+    crate::weighted::try_weighted_variance(nums, weights)
+}
+
+/// Weighted sample variance; see
+/// [`crate::weighted_sample_variance`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(
+///     Err(StatsError::NotEnoughSamples { needed: 2, got: 1 }),
+///     checked::weighted_sample_variance(&[1.0, 2.0], &[0.0, 3.0], WeightKind::Reliability)
+/// );
+/// assert_eq!(
+///     Err(StatsError::InvalidParameter("frequency weights must total more than 1")),
+///     checked::weighted_sample_variance(&[1.0, 2.0], &[0.5, 0.25], WeightKind::Frequency)
+/// );
+/// ```
+pub fn weighted_sample_variance(
+    nums: &[f64],
+    weights: &[f64],
+    kind: WeightKind,
+) -> Result<f64, StatsError> {
+    StatsError::check_pairs(nums, weights, 0)?;
+    crate::weighted::try_weighted_sample_variance(nums, weights, kind)
+}
+
+/// Weighted median; see [`crate::weighted_median`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Err(StatsError::LengthMismatch), checked::weighted_median(&[1.0], &[]));
+/// ```
+pub fn weighted_median(nums: &[f64], weights: &[f64]) -> Result<f64, StatsError> {
+    weighted_quantile(nums, weights, 0.5)
+}
+
+/// Weighted quantile; see [`crate::weighted_quantile`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Ok(2.0), checked::weighted_quantile(&[1.0, 2.0], &[1.0, 3.0], 0.5));
+/// ```
+pub fn weighted_quantile(nums: &[f64], weights: &[f64], p: f64) -> Result<f64, StatsError> {
+    weighted_quantiles(nums, weights, &[p]).map(|qs| qs[0])
+}
+
+/// Several weighted quantiles; see
+/// [`crate::weighted_quantiles`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert!(matches!(
+///     checked::weighted_quantiles(&[1.0], &[1.0], &[2.0]),
+///     Err(StatsError::InvalidParameter(_))
+/// ));
+/// ```
+pub fn weighted_quantiles(
+    nums: &[f64],
+    weights: &[f64],
+    ps: &[f64],
+) -> Result<Vec<f64>, StatsError> {
+    StatsError::check_pairs(nums, weights, 0)?;
+    crate::weighted::try_weighted_quantiles(nums, weights, ps)
+}
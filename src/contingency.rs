@@ -0,0 +1,311 @@
+// Copyright © 2019 Bader Alshaya
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Tests on counts of categorical data: chi-squared tests of
+//! goodness of fit and of independence, Fisher's exact test,
+//! and Cramér's V. A contingency table is given as a slice
+//! of its rows, which must all be the same length; the
+//! counts need not be whole, except for Fisher's test. A
+//! NaN count makes the whole result NaN.
+
+use crate::distributions::{ChiSquared, Distribution};
+use crate::{StatsError, TestResult};
+
+/// Chi-squared test of the hypothesis that the `observed`
+/// counts of some categories come from a distribution with
+/// the given `proportions`. The proportions are scaled to
+/// sum to one, so they may be given as ratios. The
+/// statistic is Pearson's `Σ (o - e)² / e`, over the
+/// observed counts o and the expected ones e; its p-value
+/// is from the chi-squared distribution with one degree of
+/// freedom fewer than the number of categories, which is
+/// only reliable when the expected counts are at least
+/// about five. The test is undefined for fewer than two
+/// categories, if the lengths differ, for negative counts,
+/// if the counts are all zero, or unless the proportions
+/// are positive and finite.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// // Mendel's peas: round yellow, wrinkled yellow, round
+/// // green and wrinkled green, expected in ratios 9:3:3:1.
+/// let test = chi_squared_goodness_of_fit(&[315.0, 108.0, 101.0, 32.0], &[9.0, 3.0, 3.0, 1.0])
+///     .unwrap();
+/// assert!((test.statistic - 0.4700239808153477).abs() < 1e-14);
+/// assert!((test.p_value - 0.925425895103616).abs() < 1e-12);
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, chi_squared_goodness_of_fit(&[1.0, 2.0], &[1.0, 0.0]));
+/// assert_eq!(None, chi_squared_goodness_of_fit(&[1.0, 2.0], &[1.0]));
+/// ```
+pub fn chi_squared_goodness_of_fit(observed: &[f64], proportions: &[f64]) -> Option<TestResult> {
+    try_chi_squared_goodness_of_fit(observed, proportions).ok()
+}
+
+/// Chi-squared goodness-of-fit test, or why it is undefined.
+pub(crate) fn try_chi_squared_goodness_of_fit(
+    observed: &[f64],
+    proportions: &[f64],
+) -> Result<TestResult, StatsError> {
+    if observed.len() != proportions.len() {
+        return Err(StatsError::LengthMismatch);
+    }
+    StatsError::check_len(observed.len(), 2)?;
+    if proportions.iter().any(|&p| !(p > 0.0 && p.is_finite())) {
+        return Err(StatsError::InvalidParameter(
+            "proportions must be positive and finite",
+        ));
+    }
+    if observed.iter().any(|x| x.is_nan()) {
+        return Ok(TestResult::NAN);
+    }
+    if observed.iter().any(|&x| x < 0.0) {
+        return Err(StatsError::NegativeValue);
+    }
+    let total: f64 = observed.iter().sum();
+    if total == 0.0 {
+        return Err(StatsError::EmptyCategory);
+    }
+    let scale = total / proportions.iter().sum::<f64>();
+    let statistic = observed
+        .iter()
+        .zip(proportions)
+        .map(|(o, p)| {
+            let e = p * scale;
+            (o - e) * (o - e) / e
+        })
+        .sum();
+    Ok(chi_squared_result(statistic, observed.len() - 1))
+}
+
+/// The test result for a chi-squared `statistic` with the
+/// given degrees of freedom.
+fn chi_squared_result(statistic: f64, degrees_of_freedom: usize) -> TestResult {
+    let p_value = ChiSquared::new(degrees_of_freedom as f64)
+        .unwrap()
+        .sf(statistic);
+    TestResult { statistic, p_value }
+}
+
+/// The totals of a contingency table.
+struct Margins {
+    /// Sum of each row.
+    rows: Vec<f64>,
+    /// Sum of each column.
+    columns: Vec<f64>,
+    /// Sum of the whole table.
+    total: f64,
+}
+
+/// The margins of a contingency table, after checking that
+/// it is a table of at least two rows and columns of
+/// counts. `Ok(None)` is for a table with a NaN count.
+fn margins(table: &[&[f64]]) -> Result<Option<Margins>, StatsError> {
+    StatsError::check_len(table.len(), 2)?;
+    let width = table[0].len();
+    if table.iter().any(|row| row.len() != width) {
+        return Err(StatsError::LengthMismatch);
+    }
+    StatsError::check_len(width, 2)?;
+    let counts = || table.iter().flat_map(|row| row.iter());
+    if counts().any(|x| x.is_nan()) {
+        return Ok(None);
+    }
+    if counts().any(|&x| x < 0.0) {
+        return Err(StatsError::NegativeValue);
+    }
+    let rows: Vec<f64> = table.iter().map(|row| row.iter().sum()).collect();
+    let columns: Vec<f64> = (0..width)
+        .map(|j| table.iter().map(|row| row[j]).sum())
+        .collect();
+    if rows.iter().chain(&columns).any(|&sum| sum == 0.0) {
+        return Err(StatsError::EmptyCategory);
+    }
+    let total = rows.iter().sum();
+    Ok(Some(Margins {
+        rows,
+        columns,
+        total,
+    }))
+}
+
+/// Pearson's chi-squared statistic of independence of a
+/// contingency table with the given margins.
+fn independence_statistic(table: &[&[f64]], margins: &Margins) -> f64 {
+    table
+        .iter()
+        .zip(&margins.rows)
+        .flat_map(|(row, r)| {
+            row.iter().zip(&margins.columns).map(move |(o, c)| {
+                let e = r * c / margins.total;
+                (o - e) * (o - e) / e
+            })
+        })
+        .sum()
+}
+
+/// Chi-squared test of the hypothesis that the row and
+/// column categories of a contingency `table` of counts are
+/// independent. The statistic is Pearson's `Σ (o - e)² / e`,
+/// where the expected count e of each cell is the product
+/// of its row and column totals over the whole total; its
+/// p-value is from the chi-squared distribution with
+/// `(rows - 1)(columns - 1)` degrees of freedom. No
+/// continuity correction is applied to 2×2 tables. The test
+/// is undefined for fewer than two rows or columns, for
+/// rows of different lengths, for negative counts, or if a
+/// row or column is all zeros.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// // Party identification by gender, from Agresti (2007).
+/// let women: &[f64] = &[762.0, 327.0, 468.0];
+/// let men: &[f64] = &[484.0, 239.0, 477.0];
+/// let test = chi_squared_independence(&[women, men]).unwrap();
+/// assert!((test.statistic - 30.07014909575467).abs() < 1e-12);
+/// assert!((test.p_value - 2.953589183211758e-7).abs() < 1e-19);
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, chi_squared_independence(&[&[1.0, 2.0], &[0.0, 0.0]]));
+/// ```
+pub fn chi_squared_independence(table: &[&[f64]]) -> Option<TestResult> {
+    try_chi_squared_independence(table).ok()
+}
+
+/// Chi-squared test of independence, or why it is undefined.
+pub(crate) fn try_chi_squared_independence(table: &[&[f64]]) -> Result<TestResult, StatsError> {
+    let margins = match margins(table)? {
+        Some(margins) => margins,
+        None => return Ok(TestResult::NAN),
+    };
+    let statistic = independence_statistic(table, &margins);
+    Ok(chi_squared_result(
+        statistic,
+        (margins.rows.len() - 1) * (margins.columns.len() - 1),
+    ))
+}
+
+/// Cramér's V, a measure of the association between the
+/// row and column categories of a contingency `table` of
+/// counts: `√(χ² / (n (k - 1)))`, where χ² is the statistic
+/// of [`chi_squared_independence`], n is the total count
+/// and k is the smaller of the numbers of rows and columns.
+/// It runs from 0 for independent categories to 1 when
+/// each determines the other. It is undefined when the
+/// chi-squared test is.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// let women: &[f64] = &[762.0, 327.0, 468.0];
+/// let men: &[f64] = &[484.0, 239.0, 477.0];
+/// let v = cramers_v(&[women, men]).unwrap();
+/// assert!((v - 0.1044358023564678).abs() < 1e-15);
+/// assert_eq!(Some(1.0), cramers_v(&[&[5.0, 0.0], &[0.0, 3.0]]));
+/// ```
+pub fn cramers_v(table: &[&[f64]]) -> Option<f64> {
+    try_cramers_v(table).ok()
+}
+
+/// Cramér's V, or why it is undefined.
+pub(crate) fn try_cramers_v(table: &[&[f64]]) -> Result<f64, StatsError> {
+    let margins = match margins(table)? {
+        Some(margins) => margins,
+        None => return Ok(f64::NAN),
+    };
+    let statistic = independence_statistic(table, &margins);
+    let k = margins.rows.len().min(margins.columns.len()) as f64;
+    Ok((statistic / (margins.total * (k - 1.0))).sqrt())
+}
+
+/// Fisher's exact test of independence of the rows and
+/// columns of a 2×2 contingency `table` of whole counts.
+/// Given the row and column totals, the count of the first
+/// cell has a hypergeometric distribution; the two-sided
+/// p-value is the total probability of the tables no more
+/// probable than the observed one, as in R's `fisher.test`.
+/// The statistic is the sample odds ratio `ad / bc` of the
+/// table `[[a, b], [c, d]]`, which is infinite if only `bc`
+/// is zero and NaN if both products are. The test is
+/// undefined unless the table is 2×2 with whole,
+/// nonnegative counts, or if a row or column is all zeros.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// // Fisher's lady tasting tea: of eight cups, four with
+/// // the milk poured first, she picked three correctly.
+/// let test = fisher_exact(&[&[3.0, 1.0], &[1.0, 3.0]]).unwrap();
+/// assert_eq!(9.0, test.statistic);
+/// assert!((test.p_value - 17.0 / 35.0).abs() < 1e-15);
+/// ```
+/// ```
+/// # use stats::*;
+/// let test = fisher_exact(&[&[1.0, 9.0], &[11.0, 3.0]]).unwrap();
+/// assert!((test.p_value - 0.002759456185220083).abs() < 1e-15);
+/// assert_eq!(None, fisher_exact(&[&[1.5, 2.0], &[1.0, 3.0]]));
+/// ```
+pub fn fisher_exact(table: &[&[f64]]) -> Option<TestResult> {
+    try_fisher_exact(table).ok()
+}
+
+/// Fisher's exact test, or why it is undefined.
+pub(crate) fn try_fisher_exact(table: &[&[f64]]) -> Result<TestResult, StatsError> {
+    if table.len() > 2 || table.iter().any(|row| row.len() > 2) {
+        return Err(StatsError::InvalidParameter(
+            "Fisher's exact test needs a 2×2 table",
+        ));
+    }
+    let (rows, columns) = match margins(table)? {
+        Some(margins) => (margins.rows, margins.columns),
+        None => return Ok(TestResult::NAN),
+    };
+    if table
+        .iter()
+        .flat_map(|row| row.iter())
+        .any(|x| x.fract() != 0.0)
+    {
+        return Err(StatsError::InvalidParameter("counts must be whole numbers"));
+    }
+    let (a, b, c, d) = (table[0][0], table[0][1], table[1][0], table[1][1]);
+    let statistic = a * d / (b * c);
+
+    // Log probabilities of each possible count x of the first
+    // cell, up to a common term, from the ratios of
+    // successive hypergeometric probabilities. Dividing by
+    // their total then gives the probabilities themselves.
+    let (low, high) = ((columns[0] - rows[1]).max(0.0), rows[0].min(columns[0]));
+    let mut ln_p = vec![0.0];
+    let mut x = low;
+    while x < high {
+        let ratio =
+            (rows[0] - x) * (columns[0] - x) / ((x + 1.0) * (rows[1] - columns[0] + x + 1.0));
+        ln_p.push(ln_p[ln_p.len() - 1] + ratio.ln());
+        x += 1.0;
+    }
+    let observed = ln_p[(a - low) as usize];
+    // Tables within a relative 1e-7 of the observed
+    // probability count as equally probable, so that
+    // rounding cannot drop exact ties.
+    let top = ln_p.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let (mut extreme, mut all) = (0.0, 0.0);
+    for &l in &ln_p {
+        let p = (l - top).exp();
+        all += p;
+        if l - observed <= 1e-7 {
+            extreme += p;
+        }
+    }
+    let p_value = (extreme / all).min(1.0);
+    Ok(TestResult { statistic, p_value })
+}
@@ -0,0 +1,328 @@
+// Copyright © 2019 Bader Alshaya
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Covariance and correlation between two variables, given
+//! as slices of paired values of equal length. All of them
+//! are undefined if the lengths differ. A NaN value makes
+//! the result NaN.
+
+use crate::accumulator::CompensatedSum;
+use crate::rank::tie_sizes;
+use crate::select::compare;
+use crate::special::{normal_two_sided, t_two_sided};
+use crate::{ranks, StatsError};
+
+/// A correlation coefficient, with the two-sided p-value of
+/// the hypothesis that the variables are uncorrelated.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Correlation {
+    /// The coefficient, between -1 and 1.
+    pub coefficient: f64,
+    /// Probability of a coefficient at least this far from
+    /// zero if the variables were uncorrelated.
+    pub p_value: f64,
+}
+
+impl Correlation {
+    /// Result for input containing NaN.
+    const NAN: Correlation = Correlation {
+        coefficient: f64::NAN,
+        p_value: f64::NAN,
+    };
+}
+
+/// Sums of squared and cross deviations from the means:
+/// `(sxx, syy, sxy)`.
+fn co_moments(xs: &[f64], ys: &[f64]) -> (f64, f64, f64) {
+    let n = xs.len() as f64;
+    let (mut sx, mut sy) = (CompensatedSum::default(), CompensatedSum::default());
+    for (&x, &y) in xs.iter().zip(ys) {
+        sx.add(x);
+        sy.add(y);
+    }
+    let (mx, my) = (sx.value() / n, sy.value() / n);
+    let (mut sxx, mut syy, mut sxy) = (
+        CompensatedSum::default(),
+        CompensatedSum::default(),
+        CompensatedSum::default(),
+    );
+    for (&x, &y) in xs.iter().zip(ys) {
+        let (dx, dy) = (x - mx, y - my);
+        sxx.add(dx * dx);
+        syy.add(dy * dy);
+        sxy.add(dx * dy);
+    }
+    (sxx.value(), syy.value(), sxy.value())
+}
+
+/// Check that `xs` and `ys` pair up and that there are at
+/// least `needed` pairs.
+fn check(xs: &[f64], ys: &[f64], needed: usize) -> Result<(), StatsError> {
+    if xs.len() != ys.len() {
+        return Err(StatsError::LengthMismatch);
+    }
+    StatsError::check_len(xs.len(), needed)
+}
+
+/// Whether any value of `xs` or `ys` is NaN.
+fn has_nan(xs: &[f64], ys: &[f64]) -> bool {
+    xs.iter().chain(ys).any(|x| x.is_nan())
+}
+
+/// Population covariance of paired values: the mean product
+/// of their deviations from their means. Computed in two
+/// passes, the first finding the means. The covariance is
+/// undefined for empty input.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(2.0), covariance(&[1.0, 3.0], &[2.0, 6.0]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, covariance(&[], &[]));
+/// assert_eq!(None, covariance(&[1.0], &[1.0, 2.0]));
+/// ```
+pub fn covariance(xs: &[f64], ys: &[f64]) -> Option<f64> {
+    check(xs, ys, 1).ok()?;
+    let (_, _, sxy) = co_moments(xs, ys);
+    Some(sxy / xs.len() as f64)
+}
+
+/// Sample covariance of paired values: the sum of the
+/// products of their deviations from their means, divided by
+/// one less than the number of pairs. The sample covariance
+/// is undefined for fewer than two pairs.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(2.0), sample_covariance(&[1.0, 2.0, 3.0], &[2.0, 4.0, 6.0]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, sample_covariance(&[1.0], &[1.0]));
+/// ```
+pub fn sample_covariance(xs: &[f64], ys: &[f64]) -> Option<f64> {
+    check(xs, ys, 2).ok()?;
+    let (_, _, sxy) = co_moments(xs, ys);
+    Some(sxy / (xs.len() - 1) as f64)
+}
+
+/// Pearson correlation coefficient, or why it is undefined.
+pub(crate) fn try_pearson(xs: &[f64], ys: &[f64]) -> Result<Correlation, StatsError> {
+    check(xs, ys, 2)?;
+    if has_nan(xs, ys) {
+        return Ok(Correlation::NAN);
+    }
+    let (sxx, syy, sxy) = co_moments(xs, ys);
+    if sxx == 0.0 || syy == 0.0 {
+        return Err(StatsError::ZeroVariance);
+    }
+    let r = (sxy / (sxx * syy).sqrt()).clamp(-1.0, 1.0);
+    Ok(Correlation {
+        coefficient: r,
+        p_value: correlation_p_value(r, xs.len()),
+    })
+}
+
+/// Two-sided p-value of a correlation coefficient `r` of
+/// `n` pairs, from the t statistic `r √((n-2)/(1-r²))` with
+/// `n - 2` degrees of freedom. Two pairs always correlate
+/// perfectly, so their p-value is 1.
+fn correlation_p_value(r: f64, n: usize) -> f64 {
+    if n <= 2 {
+        return 1.0;
+    }
+    let df = (n - 2) as f64;
+    let t = r * (df / ((1.0 - r) * (1.0 + r))).sqrt();
+    t_two_sided(t, df)
+}
+
+/// Pearson correlation coefficient of paired values: their
+/// covariance divided by the product of their standard
+/// deviations. It measures how close the pairs come to a
+/// straight line. The p-value is from the t distribution
+/// with `n - 2` degrees of freedom, which is exact for
+/// normally distributed values. The correlation is undefined
+/// for fewer than two pairs, or if either variable has all
+/// values equal.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// let xs = [1.0, 2.0, 3.0, 4.0, 5.0];
+/// let ys = [2.0, 4.0, 5.0, 4.0, 5.0];
+/// let r = pearson(&xs, &ys).unwrap();
+/// assert!((r.coefficient - 0.7745966692414834).abs() < 1e-15);
+/// assert!((r.p_value - 0.12402706265755463).abs() < 1e-12);
+/// ```
+/// ```
+/// # use stats::*;
+/// let r = pearson(&[1.0, 2.0, 3.0], &[3.0, 2.0, 1.0]).unwrap();
+/// assert_eq!((-1.0, 0.0), (r.coefficient, r.p_value));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, pearson(&[1.0, 2.0], &[5.0, 5.0]));
+/// ```
+pub fn pearson(xs: &[f64], ys: &[f64]) -> Option<Correlation> {
+    try_pearson(xs, ys).ok()
+}
+
+/// Spearman rank correlation coefficient, or why it is
+/// undefined.
+pub(crate) fn try_spearman(xs: &[f64], ys: &[f64]) -> Result<Correlation, StatsError> {
+    check(xs, ys, 2)?;
+    try_pearson(&ranks(xs), &ranks(ys))
+}
+
+/// Spearman rank correlation coefficient of paired values:
+/// the Pearson correlation of their [`ranks`], with tied
+/// values given their average rank. It measures how close
+/// the pairs come to a monotonic relationship. The p-value
+/// is from the t approximation with `n - 2` degrees of
+/// freedom. The correlation is undefined for fewer than two
+/// pairs, or if either variable has all values equal.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// // Monotonic, though not linear.
+/// let r = spearman(&[1.0, 2.0, 3.0, 4.0], &[1.0, 10.0, 100.0, 1000.0]).unwrap();
+/// assert_eq!(1.0, r.coefficient);
+/// ```
+/// ```
+/// # use stats::*;
+/// let xs = [1.0, 2.0, 3.0, 4.0, 5.0];
+/// let ys = [2.0, 4.0, 5.0, 4.0, 5.0];
+/// let r = spearman(&xs, &ys).unwrap();
+/// assert!((r.coefficient - 0.7378647873726218).abs() < 1e-15);
+/// assert!((r.p_value - 0.15461852312844922).abs() < 1e-12);
+/// ```
+pub fn spearman(xs: &[f64], ys: &[f64]) -> Option<Correlation> {
+    try_spearman(xs, ys).ok()
+}
+
+/// Sort `values` and return the number of pairs that were
+/// out of order (the number of swaps a bubble sort would
+/// make), in O(n log n) time by merge sort.
+fn sort_counting_inversions(values: &mut [f64]) -> u64 {
+    let n = values.len();
+    if n < 2 {
+        return 0;
+    }
+    let (left, right) = values.split_at_mut(n / 2);
+    let mut inversions = sort_counting_inversions(left) + sort_counting_inversions(right);
+    let mut merged = Vec::with_capacity(n);
+    let (mut i, mut j) = (0, 0);
+    while i < left.len() && j < right.len() {
+        if right[j] < left[i] {
+            inversions += (left.len() - i) as u64;
+            merged.push(right[j]);
+            j += 1;
+        } else {
+            merged.push(left[i]);
+            i += 1;
+        }
+    }
+    merged.extend_from_slice(&left[i..]);
+    merged.extend_from_slice(&right[j..]);
+    values.copy_from_slice(&merged);
+    inversions
+}
+
+/// Number of pairs among groups of tied values of the given
+/// sizes.
+fn tied_pairs(sizes: impl Iterator<Item = usize>) -> u64 {
+    sizes.map(|t| (t * (t - 1) / 2) as u64).sum()
+}
+
+/// Kendall tau-b, or why it is undefined.
+pub(crate) fn try_kendall_tau_b(xs: &[f64], ys: &[f64]) -> Result<Correlation, StatsError> {
+    check(xs, ys, 2)?;
+    if has_nan(xs, ys) {
+        return Ok(Correlation::NAN);
+    }
+    let n = xs.len();
+    // Knight's algorithm: sort by x, then by y within ties
+    // in x; the discordant pairs are then the inversions of
+    // the y values.
+    let mut pairs: Vec<(f64, f64)> = xs.iter().copied().zip(ys.iter().copied()).collect();
+    pairs.sort_unstable_by(|a, b| compare(&a.0, &b.0).then(compare(&a.1, &b.1)));
+    let x_sorted: Vec<f64> = pairs.iter().map(|p| p.0).collect();
+    let x_ties: Vec<usize> = tie_sizes(&x_sorted).collect();
+    let joint_ties = tied_pairs(pairs.chunk_by(|a, b| a == b).map(<[_]>::len));
+    let mut y_sorted: Vec<f64> = pairs.iter().map(|p| p.1).collect();
+    let discordant = sort_counting_inversions(&mut y_sorted);
+    let y_ties: Vec<usize> = tie_sizes(&y_sorted).collect();
+
+    let total = (n * (n - 1) / 2) as u64;
+    let (tx, ty) = (
+        tied_pairs(x_ties.iter().copied()),
+        tied_pairs(y_ties.iter().copied()),
+    );
+    if tx == total || ty == total {
+        return Err(StatsError::ZeroVariance);
+    }
+    // Concordant less discordant pairs.
+    let score = total as f64 - tx as f64 - ty as f64 + joint_ties as f64 - 2.0 * discordant as f64;
+    let tau = score / ((total - tx) as f64).sqrt() / ((total - ty) as f64).sqrt();
+
+    // Variance of the score with ties; see Kendall, "Rank
+    // Correlation Methods", 1970, equation 4.3.
+    let nf = n as f64;
+    let sum =
+        |ties: &[usize], f: &dyn Fn(f64) -> f64| -> f64 { ties.iter().map(|&t| f(t as f64)).sum() };
+    let v0 = nf * (nf - 1.0) * (2.0 * nf + 5.0);
+    let v_ties = |ties: &[usize]| sum(ties, &|t| t * (t - 1.0) * (2.0 * t + 5.0));
+    let pairs_x = sum(&x_ties, &|t| t * (t - 1.0));
+    let pairs_y = sum(&y_ties, &|t| t * (t - 1.0));
+    let triples_x = sum(&x_ties, &|t| t * (t - 1.0) * (t - 2.0));
+    let triples_y = sum(&y_ties, &|t| t * (t - 1.0) * (t - 2.0));
+    let mut variance = (v0 - v_ties(&x_ties) - v_ties(&y_ties)) / 18.0
+        + pairs_x * pairs_y / (2.0 * nf * (nf - 1.0));
+    if n > 2 {
+        variance += triples_x * triples_y / (9.0 * nf * (nf - 1.0) * (nf - 2.0));
+    }
+    Ok(Correlation {
+        coefficient: tau.clamp(-1.0, 1.0),
+        p_value: normal_two_sided(score / variance.sqrt()),
+    })
+}
+
+/// Kendall rank correlation coefficient tau-b of paired
+/// values: the number of concordant pairs of pairs less the
+/// number of discordant ones, scaled for ties in either
+/// variable so that it reaches ±1 whenever one ordering
+/// determines the other. Computed in O(n log n) time by
+/// Knight's algorithm. The p-value is from the normal
+/// approximation, with the variance corrected for ties. The
+/// correlation is undefined for fewer than two pairs, or if
+/// either variable has all values equal.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// let xs = [1.0, 2.0, 3.0, 4.0, 5.0];
+/// let ys = [2.0, 4.0, 5.0, 4.0, 5.0];
+/// let tau = kendall_tau_b(&xs, &ys).unwrap();
+/// assert!((tau.coefficient - 0.6708203932499369).abs() < 1e-15);
+/// assert!((tau.p_value - 0.11718508719813805).abs() < 1e-12);
+/// ```
+/// ```
+/// # use stats::*;
+/// let tau = kendall_tau_b(&[1.0, 2.0, 3.0], &[30.0, 20.0, 10.0]).unwrap();
+/// assert_eq!(-1.0, tau.coefficient);
+/// ```
+pub fn kendall_tau_b(xs: &[f64], ys: &[f64]) -> Option<Correlation> {
+    try_kendall_tau_b(xs, ys).ok()
+}
@@ -0,0 +1,209 @@
+// Copyright © 2019 Bader Alshaya
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Least-squares fits of curves other than straight lines to
+//! paired values, by linear regression on transformed
+//! values. A fit is undefined if the lengths differ. A NaN
+//! value makes the whole fit NaN.
+
+use crate::regression::try_multiple_regression;
+use crate::{Regression, StatsError};
+
+/// The shape of a fitted curve.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CurveKind {
+    /// `y = c0 + c1 x + c2 x² + … + cd x^d`.
+    Polynomial,
+    /// `y = a e^(b x)`.
+    Exponential,
+    /// `y = a x^b`.
+    PowerLaw,
+}
+
+/// A curve fitted to paired values.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CurveFit {
+    /// The shape of the curve.
+    pub kind: CurveKind,
+    /// The parameters of the curve: the coefficients `c0` to
+    /// `cd` of a polynomial, or `a` and `b` of an exponential
+    /// or power law.
+    pub parameters: Vec<f64>,
+    /// The linear regression the curve was fitted by, with
+    /// its goodness of fit. For exponential and power-law
+    /// fits it is the regression of `ln y`, so its R² and
+    /// residuals are on that scale.
+    pub regression: Regression,
+}
+
+impl CurveFit {
+    /// The fitted value of the curve at `x`.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use stats::*;
+    /// let fit = polynomial_fit(&[0.0, 1.0, 2.0, 3.0], &[1.0, 2.0, 5.0, 10.0], 2).unwrap();
+    /// assert!((fit.predict(4.0) - 17.0).abs() < 1e-12);
+    /// ```
+    pub fn predict(&self, x: f64) -> f64 {
+        match self.kind {
+            CurveKind::Polynomial => self.parameters.iter().rev().fold(0.0, |y, c| y * x + c),
+            CurveKind::Exponential => self.parameters[0] * (self.parameters[1] * x).exp(),
+            CurveKind::PowerLaw => self.parameters[0] * x.powf(self.parameters[1]),
+        }
+    }
+}
+
+/// Least-squares polynomial of the given `degree` through
+/// paired values, fitted as a multiple regression of `ys` on
+/// the powers of `xs` up to `degree`. Degree 0 fits the
+/// mean and degree 1 a straight line. The fit is undefined
+/// unless there are at least `degree + 2` pairs and `xs` has
+/// at least `degree + 1` distinct values.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// // y = 1 + x², exactly.
+/// let fit = polynomial_fit(&[0.0, 1.0, 2.0, 3.0], &[1.0, 2.0, 5.0, 10.0], 2).unwrap();
+/// for (c, expected) in fit.parameters.iter().zip([1.0, 0.0, 1.0]) {
+///     assert!((c - expected).abs() < 1e-12);
+/// }
+/// assert!((fit.regression.r_squared - 1.0).abs() < 1e-12);
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, polynomial_fit(&[0.0, 1.0, 2.0], &[1.0, 2.0, 5.0], 2));
+/// ```
+/// ```
+/// # use stats::*;
+/// // Degree 0 fits the mean.
+/// let fit = polynomial_fit(&[1.0, 2.0, 3.0], &[4.0, 6.0, 8.0], 0).unwrap();
+/// assert_eq!(vec![6.0], fit.parameters);
+/// assert_eq!(6.0, fit.predict(10.0));
+/// // A flat curve is fitted exactly, at any degree.
+/// for degree in 0..=1 {
+///     let fit = polynomial_fit(&[1.0, 2.0, 3.0], &[5.0, 5.0, 5.0], degree).unwrap();
+///     assert_eq!(5.0, fit.predict(4.0));
+///     assert!(fit.regression.r_squared.is_nan());
+/// }
+/// ```
+pub fn polynomial_fit(xs: &[f64], ys: &[f64], degree: usize) -> Option<CurveFit> {
+    try_polynomial_fit(xs, ys, degree).ok()
+}
+
+/// Polynomial fit, or why it is undefined.
+pub(crate) fn try_polynomial_fit(
+    xs: &[f64],
+    ys: &[f64],
+    degree: usize,
+) -> Result<CurveFit, StatsError> {
+    if xs.len() != ys.len() {
+        return Err(StatsError::LengthMismatch);
+    }
+    let mut powers = vec![xs.to_owned()];
+    for d in 1..degree {
+        let next = powers[d - 1].iter().zip(xs).map(|(p, x)| p * x).collect();
+        powers.push(next);
+    }
+    let predictors: Vec<&[f64]> = powers[..degree].iter().map(Vec::as_slice).collect();
+    let regression = try_multiple_regression(&predictors, ys)?;
+    Ok(CurveFit {
+        kind: CurveKind::Polynomial,
+        parameters: regression.coefficients.clone(),
+        regression,
+    })
+}
+
+/// Natural logarithms of `nums`, which must all be
+/// positive.
+fn logs(nums: &[f64]) -> Result<Vec<f64>, StatsError> {
+    if nums.iter().any(|&x| x <= 0.0) {
+        return Err(StatsError::NonPositiveValue);
+    }
+    Ok(nums.iter().map(|x| x.ln()).collect())
+}
+
+/// Exponential curve `y = a e^(b x)` through paired values,
+/// fitted as a straight line through `(x, ln y)`. This
+/// minimizes the relative rather than the absolute errors of
+/// the fitted values. The fit is undefined unless there are
+/// at least three pairs, if `xs` has all values equal, or if
+/// any of `ys` is not positive.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// // y = 3 × 2^x.
+/// let fit = exponential_fit(&[0.0, 1.0, 2.0, 3.0], &[3.0, 6.0, 12.0, 24.0]).unwrap();
+/// assert!((fit.parameters[0] - 3.0).abs() < 1e-12);
+/// assert!((fit.parameters[1] - 2f64.ln()).abs() < 1e-12);
+/// assert!((fit.predict(4.0) - 48.0).abs() < 1e-12);
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, exponential_fit(&[0.0, 1.0, 2.0], &[1.0, 0.0, 1.0]));
+/// ```
+pub fn exponential_fit(xs: &[f64], ys: &[f64]) -> Option<CurveFit> {
+    try_exponential_fit(xs, ys).ok()
+}
+
+/// Exponential fit, or why it is undefined.
+pub(crate) fn try_exponential_fit(xs: &[f64], ys: &[f64]) -> Result<CurveFit, StatsError> {
+    if xs.len() != ys.len() {
+        return Err(StatsError::LengthMismatch);
+    }
+    let regression = try_multiple_regression(&[xs], &logs(ys)?)?;
+    Ok(CurveFit {
+        kind: CurveKind::Exponential,
+        parameters: vec![regression.intercept().exp(), regression.slope()],
+        regression,
+    })
+}
+
+/// Power law `y = a x^b` through paired values, fitted as a
+/// straight line through `(ln x, ln y)`, so that `b` is the
+/// slope on a log-log plot. This minimizes the relative
+/// rather than the absolute errors of the fitted values. The
+/// fit is undefined unless there are at least three pairs,
+/// if `xs` has all values equal, or if any value is not
+/// positive.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// // Time growing as n log n is a power law of exponent a bit over 1.
+/// let ns = [1e3f64, 1e4, 1e5, 1e6];
+/// let times: Vec<f64> = ns.iter().map(|&n| 2e-9 * n * n.ln()).collect();
+/// let fit = power_law_fit(&ns, &times).unwrap();
+/// assert!(fit.parameters[1] > 1.0 && fit.parameters[1] < 1.2);
+/// assert!(fit.regression.r_squared > 0.999);
+/// ```
+/// ```
+/// # use stats::*;
+/// let fit = power_law_fit(&[1.0, 2.0, 4.0], &[5.0, 20.0, 80.0]).unwrap();
+/// assert!((fit.parameters[0] - 5.0).abs() < 1e-12);
+/// assert!((fit.parameters[1] - 2.0).abs() < 1e-12);
+/// ```
+pub fn power_law_fit(xs: &[f64], ys: &[f64]) -> Option<CurveFit> {
+    try_power_law_fit(xs, ys).ok()
+}
+
+/// Power-law fit, or why it is undefined.
+pub(crate) fn try_power_law_fit(xs: &[f64], ys: &[f64]) -> Result<CurveFit, StatsError> {
+    if xs.len() != ys.len() {
+        return Err(StatsError::LengthMismatch);
+    }
+    let regression = try_multiple_regression(&[&logs(xs)?], &logs(ys)?)?;
+    Ok(CurveFit {
+        kind: CurveKind::PowerLaw,
+        parameters: vec![regression.intercept().exp(), regression.slope()],
+        regression,
+    })
+}
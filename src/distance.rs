@@ -0,0 +1,122 @@
+// Copyright © 2019 Bader Alshaya
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Products and distances between two vectors of input
+//! values, given as slices of equal length. All of them are
+//! undefined if the lengths differ.
+
+use crate::accumulator::{accumulate, CompensatedSum};
+use crate::{Accumulator, RunningL1, RunningL2};
+
+/// The differences between corresponding values, or `None`
+/// if the lengths differ.
+fn differences<'a>(xs: &'a [f64], ys: &'a [f64]) -> Option<impl Iterator<Item = f64> + 'a> {
+    if xs.len() != ys.len() {
+        return None;
+    }
+    Some(xs.iter().zip(ys).map(|(x, y)| x - y))
+}
+
+/// Dot product of two vectors, with compensated summation.
+/// The dot product of empty vectors is 0.0.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(11.0), dot(&[1.0, 2.0], &[3.0, 4.0]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(0.0), dot(&[], &[]));
+/// assert_eq!(None, dot(&[1.0], &[]));
+/// ```
+pub fn dot(xs: &[f64], ys: &[f64]) -> Option<f64> {
+    if xs.len() != ys.len() {
+        return None;
+    }
+    let mut sum = CompensatedSum::default();
+    for (x, y) in xs.iter().zip(ys) {
+        sum.add(x * y);
+    }
+    Some(sum.value())
+}
+
+/// Euclidean distance between two vectors: the
+/// [`l2`](crate::l2) norm of their difference. The distance
+/// between empty vectors is 0.0.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(5.0), euclidean_distance(&[1.0, 1.0], &[4.0, 5.0]));
+/// ```
+pub fn euclidean_distance(xs: &[f64], ys: &[f64]) -> Option<f64> {
+    let mut acc = RunningL2::default();
+    for d in differences(xs, ys)? {
+        acc.push(d);
+    }
+    acc.result()
+}
+
+/// Manhattan distance between two vectors: the
+/// [`l1`](crate::l1) norm of their difference. The distance
+/// between empty vectors is 0.0.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(7.0), manhattan_distance(&[1.0, 1.0], &[4.0, 5.0]));
+/// ```
+pub fn manhattan_distance(xs: &[f64], ys: &[f64]) -> Option<f64> {
+    let mut acc = RunningL1::default();
+    for d in differences(xs, ys)? {
+        acc.push(d);
+    }
+    acc.result()
+}
+
+/// Cosine distance between two vectors: one less the cosine
+/// of the angle between them, so 0.0 for vectors pointing
+/// the same way, 1.0 for orthogonal vectors and 2.0 for
+/// opposite ones. Each vector is divided by its norm before
+/// the products are taken, so that large values do not
+/// overflow. The cosine distance is undefined if either
+/// vector is all zeros (including empty).
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(1.0), cosine_distance(&[1.0, 0.0], &[0.0, 3.0]));
+/// assert!((cosine_distance(&[1.0, 1.0], &[-2.0, -2.0]).unwrap() - 2.0).abs() < 1e-12);
+/// ```
+/// ```
+/// # use stats::*;
+/// assert!(cosine_distance(&[1e300, 1e300], &[2e300, 2e300]).unwrap().abs() < 1e-12);
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, cosine_distance(&[0.0, 0.0], &[1.0, 2.0]));
+/// ```
+pub fn cosine_distance(xs: &[f64], ys: &[f64]) -> Option<f64> {
+    if xs.len() != ys.len() {
+        return None;
+    }
+    let (nx, ny) = (
+        accumulate(RunningL2::default(), xs)?,
+        accumulate(RunningL2::default(), ys)?,
+    );
+    if nx == 0.0 || ny == 0.0 {
+        return None;
+    }
+    let mut sum = CompensatedSum::default();
+    for (x, y) in xs.iter().zip(ys) {
+        sum.add((x / nx) * (y / ny));
+    }
+    Some(1.0 - sum.value())
+}
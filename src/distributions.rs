@@ -0,0 +1,944 @@
+// Copyright © 2019 Bader Alshaya
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Probability distributions, for p-values, critical values
+//! and confidence intervals. Each distribution is a small
+//! value made by a `new` function that checks its
+//! parameters, and implements [`Distribution`] along with
+//! [`Continuous`] or [`Discrete`].
+//!
+//! The functions are built on the incomplete gamma and beta
+//! functions and are accurate to near double precision.
+//! Lower and upper tails are computed separately, so that
+//! [`Distribution::sf`] keeps its precision far into the
+//! upper tail where `1 - cdf` would round to zero. A NaN
+//! argument gives a NaN result.
+//!
+//! # Examples:
+//!
+//! ```
+//! # use stats::distributions::*;
+//! // The two-sided 95% critical value of t with 10 degrees of freedom.
+//! let t = StudentsT::new(10.0).unwrap();
+//! assert!((t.inverse_cdf(0.975) - 2.228138851986274).abs() < 1e-12);
+//! ```
+
+use std::f64::consts::PI;
+
+use crate::special::{
+    beta_inc_pair, beta_prefix, gamma_p, gamma_prefix, gamma_q, inverse_beta_inc, inverse_gamma_p,
+    ln_beta, normal_cdf, normal_quantile,
+};
+use crate::StatsError;
+
+/// A probability distribution on the real numbers.
+pub trait Distribution {
+    /// Cumulative distribution function: the probability of
+    /// a value at most `x`.
+    fn cdf(&self, x: f64) -> f64;
+
+    /// Survival function: the probability of a value greater
+    /// than `x`, which is `1 - cdf(x)` but computed directly
+    /// so that small upper tails keep their precision.
+    fn sf(&self, x: f64) -> f64;
+
+    /// Inverse of the cumulative distribution function, or
+    /// quantile function: the smallest value at which the
+    /// [`cdf`](Distribution::cdf) reaches `p`. This is NaN
+    /// unless `p` is between 0 and 1.
+    fn inverse_cdf(&self, p: f64) -> f64;
+
+    /// Inverse of the survival function: the smallest value
+    /// at which the [`sf`](Distribution::sf) falls to `q`,
+    /// which is `inverse_cdf(1 - q)` but keeps the precision
+    /// of a small `q`. This is NaN unless `q` is between 0 and
+    /// 1.
+    fn inverse_sf(&self, q: f64) -> f64;
+}
+
+/// A distribution with a density.
+pub trait Continuous: Distribution {
+    /// Probability density function at `x`.
+    fn pdf(&self, x: f64) -> f64;
+}
+
+/// A distribution on the whole numbers 0, 1, 2, …
+pub trait Discrete: Distribution {
+    /// Probability mass function: the probability of the
+    /// value `k`.
+    fn pmf(&self, k: u64) -> f64;
+}
+
+/// Check that a parameter is positive and finite.
+fn positive(x: f64, message: &'static str) -> Result<f64, StatsError> {
+    if x > 0.0 && x.is_finite() {
+        Ok(x)
+    } else {
+        Err(StatsError::InvalidParameter(message))
+    }
+}
+
+/// Check that a parameter is finite.
+fn finite(x: f64, message: &'static str) -> Result<f64, StatsError> {
+    if x.is_finite() {
+        Ok(x)
+    } else {
+        Err(StatsError::InvalidParameter(message))
+    }
+}
+
+/// Apply `quantile` to the probability `p` and its
+/// complement, if `p` is a probability.
+fn invert(p: f64, quantile: impl Fn(f64, f64) -> f64) -> f64 {
+    if (0.0..=1.0).contains(&p) {
+        quantile(p, 1.0 - p)
+    } else {
+        f64::NAN
+    }
+}
+
+/// The smallest whole number `k` from 0 to `upper` at which
+/// the distribution function reaches `p`, or equivalently
+/// the survival function falls to `q`, searching from
+/// `guess`. Whichever tail is smaller is compared, to keep
+/// its precision.
+fn discrete_quantile(
+    p: f64,
+    q: f64,
+    guess: f64,
+    upper: f64,
+    cdf: impl Fn(f64) -> f64,
+    sf: impl Fn(f64) -> f64,
+) -> f64 {
+    if p <= 0.0 {
+        return 0.0;
+    }
+    if q <= 0.0 {
+        return upper;
+    }
+    let reached = |k: f64| if p <= q { cdf(k) >= p } else { sf(k) <= q };
+    let mut k = guess.floor().clamp(0.0, upper);
+    if reached(k) {
+        while k > 0.0 && reached(k - 1.0) {
+            k -= 1.0;
+        }
+    } else {
+        while !reached(k) {
+            k += 1.0;
+        }
+    }
+    k
+}
+
+/// The standard normal quantile of `p`, computed from
+/// whichever of `p` and its complement `q` is smaller.
+fn z(p: f64, q: f64) -> f64 {
+    if p <= q {
+        normal_quantile(p)
+    } else {
+        -normal_quantile(q)
+    }
+}
+
+/// Normal (Gaussian) distribution.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::distributions::*;
+/// let n = Normal::standard();
+/// assert!((n.cdf(1.96) - 0.9750021048517795).abs() < 1e-15);
+/// assert!((n.inverse_cdf(0.975) - 1.959963984540054).abs() < 1e-14);
+/// // A tail far beyond what 1 - cdf could show.
+/// assert!((n.sf(10.0) / 7.619853024160527e-24 - 1.0).abs() < 1e-13);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Normal {
+    mean: f64,
+    stddev: f64,
+}
+
+impl Normal {
+    /// The normal distribution with the given mean and
+    /// standard deviation, which must be positive.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use stats::distributions::*;
+    /// let n = Normal::new(100.0, 15.0).unwrap();
+    /// assert!((n.cdf(130.0) - Normal::standard().cdf(2.0)).abs() < 1e-15);
+    /// assert!(Normal::new(0.0, 0.0).is_err());
+    /// ```
+    pub fn new(mean: f64, stddev: f64) -> Result<Normal, StatsError> {
+        Ok(Normal {
+            mean: finite(mean, "mean must be finite")?,
+            stddev: positive(stddev, "standard deviation must be positive")?,
+        })
+    }
+
+    /// The standard normal distribution, of mean 0 and
+    /// standard deviation 1.
+    pub fn standard() -> Normal {
+        Normal {
+            mean: 0.0,
+            stddev: 1.0,
+        }
+    }
+}
+
+impl Distribution for Normal {
+    fn cdf(&self, x: f64) -> f64 {
+        normal_cdf((x - self.mean) / self.stddev)
+    }
+
+    fn sf(&self, x: f64) -> f64 {
+        normal_cdf((self.mean - x) / self.stddev)
+    }
+
+    fn inverse_cdf(&self, p: f64) -> f64 {
+        invert(p, |p, q| self.quantile(p, q))
+    }
+
+    fn inverse_sf(&self, q: f64) -> f64 {
+        invert(q, |q, p| self.quantile(p, q))
+    }
+}
+
+impl Continuous for Normal {
+    fn pdf(&self, x: f64) -> f64 {
+        let z = (x - self.mean) / self.stddev;
+        (-0.5 * z * z).exp() / (self.stddev * (2.0 * PI).sqrt())
+    }
+}
+
+impl Normal {
+    /// The value with `p` below it and `q` above.
+    fn quantile(&self, p: f64, q: f64) -> f64 {
+        if p == 0.0 {
+            f64::NEG_INFINITY
+        } else if q == 0.0 {
+            f64::INFINITY
+        } else {
+            self.mean + self.stddev * z(p, q)
+        }
+    }
+}
+
+/// Log-normal distribution: the distribution of `e^X` for a
+/// normally distributed `X`.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::distributions::*;
+/// let d = LogNormal::new(0.0, 1.0).unwrap();
+/// assert_eq!(0.5, d.cdf(1.0));
+/// assert!((d.pdf(1.0) - 0.3989422804014327).abs() < 1e-15);
+/// assert!((d.inverse_cdf(0.975) - 1.959963984540054f64.exp()).abs() < 1e-13);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LogNormal {
+    mu: f64,
+    sigma: f64,
+}
+
+impl LogNormal {
+    /// The log-normal distribution whose logarithm has mean
+    /// `mu` and standard deviation `sigma`, which must be
+    /// positive.
+    pub fn new(mu: f64, sigma: f64) -> Result<LogNormal, StatsError> {
+        Ok(LogNormal {
+            mu: finite(mu, "mu must be finite")?,
+            sigma: positive(sigma, "sigma must be positive")?,
+        })
+    }
+
+    /// The value with `p` below it and `q` above.
+    fn quantile(&self, p: f64, q: f64) -> f64 {
+        if p == 0.0 {
+            0.0
+        } else if q == 0.0 {
+            f64::INFINITY
+        } else {
+            (self.mu + self.sigma * z(p, q)).exp()
+        }
+    }
+}
+
+impl Distribution for LogNormal {
+    fn cdf(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            return 0.0;
+        }
+        normal_cdf((x.ln() - self.mu) / self.sigma)
+    }
+
+    fn sf(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            return 1.0;
+        }
+        normal_cdf((self.mu - x.ln()) / self.sigma)
+    }
+
+    fn inverse_cdf(&self, p: f64) -> f64 {
+        invert(p, |p, q| self.quantile(p, q))
+    }
+
+    fn inverse_sf(&self, q: f64) -> f64 {
+        invert(q, |q, p| self.quantile(p, q))
+    }
+}
+
+impl Continuous for LogNormal {
+    fn pdf(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            return 0.0;
+        }
+        let z = (x.ln() - self.mu) / self.sigma;
+        (-0.5 * z * z).exp() / (x * self.sigma * (2.0 * PI).sqrt())
+    }
+}
+
+/// Student's t distribution.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::distributions::*;
+/// let t = StudentsT::new(5.0).unwrap();
+/// assert!((t.sf(2.015048373333024) - 0.05).abs() < 1e-15);
+/// assert!((t.inverse_sf(0.05) - 2.015048373333024).abs() < 1e-13);
+/// assert_eq!(0.5, t.cdf(0.0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StudentsT {
+    df: f64,
+}
+
+impl StudentsT {
+    /// Student's t distribution with `df` degrees of freedom,
+    /// which must be positive but need not be whole.
+    pub fn new(df: f64) -> Result<StudentsT, StatsError> {
+        Ok(StudentsT {
+            df: positive(df, "degrees of freedom must be positive")?,
+        })
+    }
+
+    /// The probability of a value below -|t|.
+    fn tail(&self, t: f64) -> f64 {
+        if t.is_infinite() {
+            return 0.0;
+        }
+        let (t2, df) = (t * t, self.df);
+        0.5 * beta_inc_pair(df / 2.0, 0.5, df / (df + t2), t2 / (df + t2)).0
+    }
+
+    /// The value with `p` below it and `q` above.
+    fn quantile(&self, p: f64, q: f64) -> f64 {
+        if p == 0.0 {
+            return f64::NEG_INFINITY;
+        }
+        if q == 0.0 {
+            return f64::INFINITY;
+        }
+        // Solve for the smaller tail s = I_x(df/2, 1/2) / 2,
+        // where x = df / (df + t²), through whichever of x and
+        // 1 - x is the smaller.
+        let s = 2.0 * p.min(q);
+        let (a, df) = (self.df / 2.0, self.df);
+        let y = inverse_beta_inc(0.5, a, 1.0 - s, s);
+        let t = if y < 0.5 {
+            (df * y / (1.0 - y)).sqrt()
+        } else {
+            let x = inverse_beta_inc(a, 0.5, s, 1.0 - s);
+            (df * (1.0 - x) / x).sqrt()
+        };
+        if p < q {
+            -t
+        } else {
+            t
+        }
+    }
+}
+
+impl Distribution for StudentsT {
+    fn cdf(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            self.tail(x)
+        } else {
+            1.0 - self.tail(x)
+        }
+    }
+
+    fn sf(&self, x: f64) -> f64 {
+        self.cdf(-x)
+    }
+
+    fn inverse_cdf(&self, p: f64) -> f64 {
+        invert(p, |p, q| self.quantile(p, q))
+    }
+
+    fn inverse_sf(&self, q: f64) -> f64 {
+        invert(q, |q, p| self.quantile(p, q))
+    }
+}
+
+impl Continuous for StudentsT {
+    fn pdf(&self, x: f64) -> f64 {
+        let df = self.df;
+        (-(df + 1.0) / 2.0 * (x * x / df).ln_1p() - ln_beta(df / 2.0, 0.5)).exp() / df.sqrt()
+    }
+}
+
+/// Gamma distribution, with a shape and a scale parameter.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::distributions::*;
+/// // Shape 1 is the exponential distribution.
+/// let g = Gamma::new(1.0, 2.0).unwrap();
+/// let e = Exponential::new(0.5).unwrap();
+/// assert!((g.cdf(3.0) - e.cdf(3.0)).abs() < 1e-15);
+/// ```
+/// ```
+/// # use stats::distributions::*;
+/// let g = Gamma::new(3.0, 1.0).unwrap();
+/// assert!((g.pdf(2.0) - 2.0 * (-2.0f64).exp()).abs() < 1e-15);
+/// assert!((g.inverse_cdf(g.cdf(4.5)) - 4.5).abs() < 1e-13);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Gamma {
+    shape: f64,
+    scale: f64,
+}
+
+impl Gamma {
+    /// The gamma distribution with the given shape and
+    /// scale, which must both be positive. Its mean is
+    /// `shape × scale`.
+    pub fn new(shape: f64, scale: f64) -> Result<Gamma, StatsError> {
+        Ok(Gamma {
+            shape: positive(shape, "shape must be positive")?,
+            scale: positive(scale, "scale must be positive")?,
+        })
+    }
+
+    /// The value with `p` below it and `q` above.
+    fn quantile(&self, p: f64, q: f64) -> f64 {
+        self.scale * inverse_gamma_p(self.shape, p, q)
+    }
+}
+
+impl Distribution for Gamma {
+    fn cdf(&self, x: f64) -> f64 {
+        gamma_p(self.shape, x / self.scale)
+    }
+
+    fn sf(&self, x: f64) -> f64 {
+        gamma_q(self.shape, x / self.scale)
+    }
+
+    fn inverse_cdf(&self, p: f64) -> f64 {
+        invert(p, |p, q| self.quantile(p, q))
+    }
+
+    fn inverse_sf(&self, q: f64) -> f64 {
+        invert(q, |q, p| self.quantile(p, q))
+    }
+}
+
+impl Continuous for Gamma {
+    fn pdf(&self, x: f64) -> f64 {
+        let (a, x) = (self.shape, x / self.scale);
+        if x < 0.0 {
+            0.0
+        } else if x == 0.0 {
+            match a.partial_cmp(&1.0) {
+                Some(std::cmp::Ordering::Less) => f64::INFINITY,
+                Some(std::cmp::Ordering::Equal) => 1.0 / self.scale,
+                _ => 0.0,
+            }
+        } else if x.is_infinite() {
+            0.0
+        } else {
+            gamma_prefix(a, x) / (x * self.scale)
+        }
+    }
+}
+
+/// Chi-squared distribution: the distribution of the sum of
+/// the squares of independent standard normal values.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::distributions::*;
+/// let c = ChiSquared::new(1.0).unwrap();
+/// let n = Normal::standard();
+/// assert!((c.sf(1.96 * 1.96) - 2.0 * n.sf(1.96)).abs() < 1e-15);
+/// assert!((ChiSquared::new(10.0).unwrap().inverse_sf(0.05) - 18.307038053275146).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChiSquared {
+    gamma: Gamma,
+}
+
+impl ChiSquared {
+    /// The chi-squared distribution with `df` degrees of
+    /// freedom, which must be positive but need not be
+    /// whole. It is the gamma distribution of shape `df / 2`
+    /// and scale 2.
+    pub fn new(df: f64) -> Result<ChiSquared, StatsError> {
+        let df = positive(df, "degrees of freedom must be positive")?;
+        Ok(ChiSquared {
+            gamma: Gamma::new(df / 2.0, 2.0)?,
+        })
+    }
+}
+
+impl Distribution for ChiSquared {
+    fn cdf(&self, x: f64) -> f64 {
+        self.gamma.cdf(x)
+    }
+
+    fn sf(&self, x: f64) -> f64 {
+        self.gamma.sf(x)
+    }
+
+    fn inverse_cdf(&self, p: f64) -> f64 {
+        self.gamma.inverse_cdf(p)
+    }
+
+    fn inverse_sf(&self, q: f64) -> f64 {
+        self.gamma.inverse_sf(q)
+    }
+}
+
+impl Continuous for ChiSquared {
+    fn pdf(&self, x: f64) -> f64 {
+        self.gamma.pdf(x)
+    }
+}
+
+/// Snedecor's F distribution: the distribution of the ratio
+/// of two independent chi-squared values, each divided by
+/// its degrees of freedom.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::distributions::*;
+/// let f = F::new(5.0, 10.0).unwrap();
+/// assert!((f.inverse_sf(0.05) - 3.325834530413011).abs() < 1e-12);
+/// assert!((f.sf(3.325834530413011) - 0.05).abs() < 1e-15);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct F {
+    df1: f64,
+    df2: f64,
+}
+
+impl F {
+    /// The F distribution with `df1` degrees of freedom in
+    /// the numerator and `df2` in the denominator, which
+    /// must both be positive.
+    pub fn new(df1: f64, df2: f64) -> Result<F, StatsError> {
+        Ok(F {
+            df1: positive(df1, "degrees of freedom must be positive")?,
+            df2: positive(df2, "degrees of freedom must be positive")?,
+        })
+    }
+
+    /// I_u(df1/2, df2/2) and its complement, where u is the
+    /// beta variate df1 x / (df1 x + df2).
+    fn tails(&self, x: f64) -> (f64, f64) {
+        if x <= 0.0 {
+            return (0.0, 1.0);
+        }
+        if x.is_infinite() {
+            return (1.0, 0.0);
+        }
+        let (d1, d2) = (self.df1, self.df2);
+        let denominator = d1 * x + d2;
+        beta_inc_pair(d1 / 2.0, d2 / 2.0, d1 * x / denominator, d2 / denominator)
+    }
+
+    /// The value with `p` below it and `q` above.
+    fn quantile(&self, p: f64, q: f64) -> f64 {
+        let (d1, d2) = (self.df1, self.df2);
+        let (a, b) = (d1 / 2.0, d2 / 2.0);
+        // Solve for the beta variate u or its complement,
+        // whichever is smaller.
+        let u = inverse_beta_inc(a, b, p, q);
+        if u < 0.5 {
+            d2 * u / (d1 * (1.0 - u))
+        } else {
+            let v = inverse_beta_inc(b, a, q, p);
+            d2 * (1.0 - v) / (d1 * v)
+        }
+    }
+}
+
+impl Distribution for F {
+    fn cdf(&self, x: f64) -> f64 {
+        self.tails(x).0
+    }
+
+    fn sf(&self, x: f64) -> f64 {
+        self.tails(x).1
+    }
+
+    fn inverse_cdf(&self, p: f64) -> f64 {
+        invert(p, |p, q| self.quantile(p, q))
+    }
+
+    fn inverse_sf(&self, q: f64) -> f64 {
+        invert(q, |q, p| self.quantile(p, q))
+    }
+}
+
+impl Continuous for F {
+    fn pdf(&self, x: f64) -> f64 {
+        let (d1, d2) = (self.df1, self.df2);
+        if x < 0.0 || x.is_infinite() {
+            0.0
+        } else if x == 0.0 {
+            match d1.partial_cmp(&2.0) {
+                Some(std::cmp::Ordering::Less) => f64::INFINITY,
+                Some(std::cmp::Ordering::Equal) => 1.0,
+                _ => 0.0,
+            }
+        } else {
+            let denominator = d1 * x + d2;
+            beta_prefix(d1 / 2.0, d2 / 2.0, d1 * x / denominator, d2 / denominator) / x
+        }
+    }
+}
+
+/// Exponential distribution: the waiting time between the
+/// events of a Poisson process.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::distributions::*;
+/// let e = Exponential::new(2.0).unwrap();
+/// assert!((e.cdf(1.0) - (1.0 - (-2.0f64).exp())).abs() < 1e-15);
+/// assert!((e.inverse_cdf(0.5) - 2f64.ln() / 2.0).abs() < 1e-15);
+/// assert_eq!(2.0, e.pdf(0.0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Exponential {
+    rate: f64,
+}
+
+impl Exponential {
+    /// The exponential distribution with the given rate,
+    /// which must be positive. Its mean is `1 / rate`.
+    pub fn new(rate: f64) -> Result<Exponential, StatsError> {
+        Ok(Exponential {
+            rate: positive(rate, "rate must be positive")?,
+        })
+    }
+
+    /// The value with `p` below it and `q` above.
+    fn quantile(&self, p: f64, q: f64) -> f64 {
+        if p <= q {
+            -(-p).ln_1p() / self.rate
+        } else {
+            -q.ln() / self.rate
+        }
+    }
+}
+
+impl Distribution for Exponential {
+    fn cdf(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            return 0.0;
+        }
+        -(-self.rate * x).exp_m1()
+    }
+
+    fn sf(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            return 1.0;
+        }
+        (-self.rate * x).exp()
+    }
+
+    fn inverse_cdf(&self, p: f64) -> f64 {
+        invert(p, |p, q| self.quantile(p, q))
+    }
+
+    fn inverse_sf(&self, q: f64) -> f64 {
+        invert(q, |q, p| self.quantile(p, q))
+    }
+}
+
+impl Continuous for Exponential {
+    fn pdf(&self, x: f64) -> f64 {
+        if x < 0.0 {
+            return 0.0;
+        }
+        self.rate * (-self.rate * x).exp()
+    }
+}
+
+/// Beta distribution on the interval from 0 to 1.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::distributions::*;
+/// let b = Beta::new(2.0, 3.0).unwrap();
+/// assert!((b.cdf(0.5) - 0.6875).abs() < 1e-15);
+/// assert!((b.pdf(0.5) - 1.5).abs() < 1e-14);
+/// assert!((b.inverse_cdf(0.6875) - 0.5).abs() < 1e-14);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Beta {
+    a: f64,
+    b: f64,
+}
+
+impl Beta {
+    /// The beta distribution with shape parameters `a` and
+    /// `b`, which must both be positive. Its mean is
+    /// `a / (a + b)`.
+    pub fn new(a: f64, b: f64) -> Result<Beta, StatsError> {
+        Ok(Beta {
+            a: positive(a, "shape must be positive")?,
+            b: positive(b, "shape must be positive")?,
+        })
+    }
+
+    /// The density at an end of the interval where the
+    /// power of the distance to it is `shape - 1`.
+    fn end_density(&self, shape: f64) -> f64 {
+        match shape.partial_cmp(&1.0) {
+            Some(std::cmp::Ordering::Less) => f64::INFINITY,
+            Some(std::cmp::Ordering::Equal) => (-ln_beta(self.a, self.b)).exp(),
+            _ => 0.0,
+        }
+    }
+}
+
+impl Distribution for Beta {
+    fn cdf(&self, x: f64) -> f64 {
+        beta_inc_pair(self.a, self.b, x, 1.0 - x).0
+    }
+
+    fn sf(&self, x: f64) -> f64 {
+        beta_inc_pair(self.a, self.b, x, 1.0 - x).1
+    }
+
+    fn inverse_cdf(&self, p: f64) -> f64 {
+        invert(p, |p, q| inverse_beta_inc(self.a, self.b, p, q))
+    }
+
+    fn inverse_sf(&self, q: f64) -> f64 {
+        invert(q, |q, p| inverse_beta_inc(self.a, self.b, p, q))
+    }
+}
+
+impl Continuous for Beta {
+    fn pdf(&self, x: f64) -> f64 {
+        if !(0.0..=1.0).contains(&x) {
+            0.0
+        } else if x == 0.0 {
+            self.end_density(self.a)
+        } else if x == 1.0 {
+            self.end_density(self.b)
+        } else {
+            beta_prefix(self.a, self.b, x, 1.0 - x) / (x * (1.0 - x))
+        }
+    }
+}
+
+/// Poisson distribution: the number of events in a fixed
+/// interval when they occur independently at a constant
+/// rate.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::distributions::*;
+/// let p = Poisson::new(2.0).unwrap();
+/// assert!((p.pmf(0) - (-2.0f64).exp()).abs() < 1e-15);
+/// assert!((p.cdf(3.0) - 19.0 / 3.0 * (-2.0f64).exp()).abs() < 1e-15);
+/// assert_eq!(2.0, p.inverse_cdf(0.5));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Poisson {
+    mean: f64,
+}
+
+impl Poisson {
+    /// The Poisson distribution with the given mean number
+    /// of events, which must be positive.
+    pub fn new(mean: f64) -> Result<Poisson, StatsError> {
+        Ok(Poisson {
+            mean: positive(mean, "mean must be positive")?,
+        })
+    }
+
+    /// The value with `p` below or at it and `q` above.
+    fn quantile(&self, p: f64, q: f64) -> f64 {
+        let m = self.mean;
+        let guess = if p <= 0.0 || q <= 0.0 {
+            0.0
+        } else {
+            // Cornish-Fisher expansion.
+            let z = z(p, q);
+            m + m.sqrt() * z + (z * z - 1.0) / 6.0
+        };
+        discrete_quantile(p, q, guess, f64::INFINITY, |k| self.cdf(k), |k| self.sf(k))
+    }
+}
+
+impl Distribution for Poisson {
+    fn cdf(&self, x: f64) -> f64 {
+        if x < 0.0 {
+            return 0.0;
+        }
+        if x == f64::INFINITY {
+            return 1.0;
+        }
+        gamma_q(x.floor() + 1.0, self.mean)
+    }
+
+    fn sf(&self, x: f64) -> f64 {
+        if x < 0.0 {
+            return 1.0;
+        }
+        if x == f64::INFINITY {
+            return 0.0;
+        }
+        gamma_p(x.floor() + 1.0, self.mean)
+    }
+
+    fn inverse_cdf(&self, p: f64) -> f64 {
+        invert(p, |p, q| self.quantile(p, q))
+    }
+
+    fn inverse_sf(&self, q: f64) -> f64 {
+        invert(q, |q, p| self.quantile(p, q))
+    }
+}
+
+impl Discrete for Poisson {
+    fn pmf(&self, k: u64) -> f64 {
+        gamma_prefix(k as f64 + 1.0, self.mean) / self.mean
+    }
+}
+
+/// Binomial distribution: the number of successes in a
+/// fixed number of independent trials with the same
+/// probability of success.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::distributions::*;
+/// let b = Binomial::new(10, 0.5).unwrap();
+/// assert!((b.pmf(5) - 252.0 / 1024.0).abs() < 1e-15);
+/// assert!((b.cdf(3.0) - 176.0 / 1024.0).abs() < 1e-15);
+/// assert_eq!(5.0, b.inverse_cdf(0.5));
+/// ```
+/// ```
+/// # use stats::distributions::*;
+/// assert!(Binomial::new(10, 1.5).is_err());
+/// // With no trials there are surely no successes.
+/// assert_eq!(1.0, Binomial::new(0, 1.0).unwrap().pmf(0));
+/// assert_eq!(1.0, Binomial::new(0, 0.0).unwrap().pmf(0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Binomial {
+    trials: u64,
+    p: f64,
+}
+
+impl Binomial {
+    /// The binomial distribution of `trials` trials, each a
+    /// success with probability `p`.
+    pub fn new(trials: u64, p: f64) -> Result<Binomial, StatsError> {
+        if !(0.0..=1.0).contains(&p) {
+            return Err(StatsError::InvalidParameter(
+                "probability must be between 0 and 1",
+            ));
+        }
+        Ok(Binomial { trials, p })
+    }
+
+    /// I_p(k + 1, n - k) and its complement, which are the
+    /// probabilities of more than `k` successes and of at
+    /// most `k`.
+    fn tails(&self, x: f64) -> (f64, f64) {
+        let n = self.trials as f64;
+        if x < 0.0 {
+            return (1.0, 0.0);
+        }
+        let k = x.floor();
+        if k >= n {
+            return (0.0, 1.0);
+        }
+        beta_inc_pair(k + 1.0, n - k, self.p, 1.0 - self.p)
+    }
+
+    /// The value with `p` below or at it and `q` above.
+    fn quantile(&self, p: f64, q: f64) -> f64 {
+        let n = self.trials as f64;
+        let (s, f) = (self.p, 1.0 - self.p);
+        let guess = if p <= 0.0 || q <= 0.0 {
+            0.0
+        } else {
+            // Cornish-Fisher expansion.
+            let z = z(p, q);
+            n * s + (n * s * f).sqrt() * z + (f - s) * (z * z - 1.0) / 6.0
+        };
+        discrete_quantile(p, q, guess, n, |k| self.cdf(k), |k| self.sf(k))
+    }
+}
+
+impl Distribution for Binomial {
+    fn cdf(&self, x: f64) -> f64 {
+        self.tails(x).1
+    }
+
+    fn sf(&self, x: f64) -> f64 {
+        self.tails(x).0
+    }
+
+    fn inverse_cdf(&self, p: f64) -> f64 {
+        invert(p, |p, q| self.quantile(p, q))
+    }
+
+    fn inverse_sf(&self, q: f64) -> f64 {
+        invert(q, |q, p| self.quantile(p, q))
+    }
+}
+
+impl Discrete for Binomial {
+    fn pmf(&self, k: u64) -> f64 {
+        let n = self.trials;
+        let (s, f) = (self.p, 1.0 - self.p);
+        if k > n {
+            0.0
+        } else if n == 0 {
+            1.0
+        } else if k == 0 {
+            (n as f64 * (-s).ln_1p()).exp()
+        } else if k == n {
+            (n as f64 * s.ln()).exp()
+        } else if s == 0.0 || f == 0.0 {
+            0.0
+        } else {
+            let (k, n) = (k as f64, n as f64);
+            beta_prefix(k, n - k, s, f) * n / (k * (n - k))
+        }
+    }
+}
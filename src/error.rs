@@ -0,0 +1,119 @@
+// Copyright © 2019 Bader Alshaya
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Errors reported by the [`checked`](crate::checked)
+//! statistics.
+
+use std::fmt;
+
+/// Why a statistic could not be computed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum StatsError {
+    /// There were no input values, and the statistic is
+    /// undefined for an empty list.
+    EmptyInput,
+    /// An input value was NaN.
+    ContainsNan,
+    /// There were some input values, but fewer than the
+    /// statistic needs.
+    NotEnoughSamples {
+        /// Number of values the statistic needs.
+        needed: usize,
+        /// Number of values given.
+        got: usize,
+    },
+    /// The input values were all equal, and the statistic
+    /// is scaled by their spread.
+    ZeroVariance,
+    /// An input value was negative, and the statistic is
+    /// only defined for values of zero or more.
+    NegativeValue,
+    /// An input value was zero or negative, and the
+    /// statistic takes its logarithm.
+    NonPositiveValue,
+    /// Several values were equally the most common, and the
+    /// statistic needs a single one.
+    Multimodal,
+    /// Two inputs that should pair up value for value had
+    /// different lengths.
+    LengthMismatch,
+    /// An input vector was all zeros, and the statistic
+    /// needs its direction.
+    ZeroNorm,
+    /// A predictor of a regression was a linear combination
+    /// of the others, so their coefficients cannot be told
+    /// apart.
+    Collinear,
+    /// A row or column of a table of counts, or a category
+    /// of expected counts, was all zeros, so a chi-squared
+    /// statistic would divide by zero.
+    EmptyCategory,
+    /// A parameter of the statistic, such as the probability
+    /// of a quantile, was out of range. The message says
+    /// which.
+    InvalidParameter(&'static str),
+}
+
+impl StatsError {
+    /// Check that `nums` contains no NaN and at least
+    /// `needed` values.
+    pub(crate) fn check(nums: &[f64], needed: usize) -> Result<(), StatsError> {
+        if nums.iter().any(|x| x.is_nan()) {
+            Err(StatsError::ContainsNan)
+        } else {
+            StatsError::check_len(nums.len(), needed)
+        }
+    }
+
+    /// Check that `xs` and `ys` contain no NaN, pair up, and
+    /// have at least `needed` values each.
+    pub(crate) fn check_pairs(xs: &[f64], ys: &[f64], needed: usize) -> Result<(), StatsError> {
+        if xs.len() != ys.len() {
+            return Err(StatsError::LengthMismatch);
+        }
+        StatsError::check(xs, needed)?;
+        StatsError::check(ys, needed)
+    }
+
+    /// Check that `got` values are at least the `needed`
+    /// ones.
+    pub(crate) fn check_len(got: usize, needed: usize) -> Result<(), StatsError> {
+        if got >= needed {
+            Ok(())
+        } else if got == 0 {
+            Err(StatsError::EmptyInput)
+        } else {
+            Err(StatsError::NotEnoughSamples { needed, got })
+        }
+    }
+}
+
+impl fmt::Display for StatsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StatsError::EmptyInput => write!(f, "empty input"),
+            StatsError::ContainsNan => write!(f, "input contains NaN"),
+            StatsError::NotEnoughSamples { needed, got } => write!(
+                f,
+                "not enough samples: need at least {}, got {}",
+                needed, got
+            ),
+            StatsError::ZeroVariance => write!(f, "all values are equal"),
+            StatsError::NegativeValue => write!(f, "input contains a negative value"),
+            StatsError::NonPositiveValue => {
+                write!(f, "input contains a value that is not positive")
+            }
+            StatsError::Multimodal => write!(f, "no unique mode"),
+            StatsError::LengthMismatch => write!(f, "inputs differ in length"),
+            StatsError::ZeroNorm => write!(f, "input vector is zero"),
+            StatsError::Collinear => write!(f, "predictors are linearly dependent"),
+            StatsError::EmptyCategory => write!(f, "a category has no counts"),
+            StatsError::InvalidParameter(what) => write!(f, "invalid parameter: {}", what),
+        }
+    }
+}
+
+impl std::error::Error for StatsError {}
@@ -0,0 +1,624 @@
+// Copyright © 2019 Bader Alshaya
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Statistics on any numeric input: slices, `Vec`s or
+//! iterators of integers or floats of any width, or of
+//! references to them. Each function here computes the same
+//! value as the function of the same name at the crate root,
+//! which takes `&[f64]`.
+//!
+//! Values are converted to `f64` one at a time, and all
+//! accumulation is done in `f64`, which has the range to
+//! hold the sum of any number of 64-bit integers without
+//! overflow. Integers beyond 2^53 in magnitude are rounded
+//! to the nearest `f64`. The statistics that can be computed
+//! incrementally never hold more than one converted value;
+//! the ones that need the whole input, such as the median,
+//! collect a single `Vec<f64>`.
+
+use crate::{Accumulator, MedianPolicy, ModePolicy, QuantileMethod, Summary};
+
+/// A number that statistics can be computed on.
+pub trait Numeric: Copy {
+    /// The value as an `f64`, rounded to the nearest `f64`
+    /// if it has no exact representation.
+    fn to_f64(self) -> f64;
+}
+
+macro_rules! numeric {
+    ($($t:ty),*) => {
+        $(impl Numeric for $t {
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+        })*
+    };
+}
+
+numeric!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64);
+
+impl<T: Numeric> Numeric for &T {
+    fn to_f64(self) -> f64 {
+        (*self).to_f64()
+    }
+}
+
+/// The input values converted to `f64`.
+fn to_vec<I>(nums: I) -> Vec<f64>
+where
+    I: IntoIterator,
+    I::Item: Numeric,
+{
+    nums.into_iter().map(Numeric::to_f64).collect()
+}
+
+/// Push all of `nums` into `acc` and return its result.
+fn accumulate<A, I>(mut acc: A, nums: I) -> Option<f64>
+where
+    A: Accumulator,
+    I: IntoIterator,
+    I::Item: Numeric,
+{
+    acc.push_all(nums);
+    acc.result()
+}
+
+/// Arithmetic mean; see [`crate::mean`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(2.0), generic::mean(&[1u8, 2, 3]));
+/// ```
+/// ```
+/// # use stats::*;
+/// // The sum overflows `i64`, but not the `f64` accumulator.
+/// assert_eq!(Some(i64::MAX as f64), generic::mean(vec![i64::MAX; 4]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(4.5), generic::mean((0..10).map(|i| i as f32)));
+/// ```
+pub fn mean<I>(nums: I) -> Option<f64>
+where
+    I: IntoIterator,
+    I::Item: Numeric,
+{
+    accumulate(crate::RunningMean::default(), nums)
+}
+
+/// Geometric mean; see [`crate::geometric_mean`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(0.0), generic::geometric_mean(&[0u32, 7]));
+/// ```
+pub fn geometric_mean<I>(nums: I) -> Option<f64>
+where
+    I: IntoIterator,
+    I::Item: Numeric,
+{
+    accumulate(crate::RunningGeometricMean::default(), nums)
+}
+
+/// Harmonic mean; see [`crate::harmonic_mean`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(2.0), generic::harmonic_mean(&[1u8, 4, 4]));
+/// ```
+pub fn harmonic_mean<I>(nums: I) -> Option<f64>
+where
+    I: IntoIterator,
+    I::Item: Numeric,
+{
+    accumulate(crate::RunningHarmonicMean::default(), nums)
+}
+
+/// Power mean; see [`crate::power_mean`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(5.0), generic::power_mean(&[1i64, 7], 2.0));
+/// ```
+pub fn power_mean<I>(nums: I, p: f64) -> Option<f64>
+where
+    I: IntoIterator,
+    I::Item: Numeric,
+{
+    crate::power_mean(&to_vec(nums), p)
+}
+
+/// Population standard deviation; see [`crate::stddev`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(2.0), generic::stddev(&[2u32, 4, 4, 4, 5, 5, 7, 9]));
+/// ```
+pub fn stddev<I>(nums: I) -> Option<f64>
+where
+    I: IntoIterator,
+    I::Item: Numeric,
+{
+    accumulate(crate::RunningStddev::default(), nums)
+}
+
+/// Population variance; see [`crate::variance`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(4.0), generic::variance(&[2i16, 4, 4, 4, 5, 5, 7, 9]));
+/// ```
+pub fn variance<I>(nums: I) -> Option<f64>
+where
+    I: IntoIterator,
+    I::Item: Numeric,
+{
+    accumulate(crate::RunningVariance::default(), nums)
+}
+
+/// Sample variance; see [`crate::sample_variance`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(2.0), generic::sample_variance(&[1i64, 3]));
+/// ```
+pub fn sample_variance<I>(nums: I) -> Option<f64>
+where
+    I: IntoIterator,
+    I::Item: Numeric,
+{
+    accumulate(crate::RunningVariance::sample(), nums)
+}
+
+/// Sample standard deviation; see [`crate::sample_stddev`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(2.0), generic::sample_stddev(&[1usize, 3, 5]));
+/// ```
+pub fn sample_stddev<I>(nums: I) -> Option<f64>
+where
+    I: IntoIterator,
+    I::Item: Numeric,
+{
+    accumulate(crate::RunningStddev::sample(), nums)
+}
+
+/// L1 norm; see [`crate::l1`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(7.0), generic::l1(&[-3i8, 4]));
+/// ```
+pub fn l1<I>(nums: I) -> Option<f64>
+where
+    I: IntoIterator,
+    I::Item: Numeric,
+{
+    accumulate(crate::RunningL1::default(), nums)
+}
+
+/// L2 norm; see [`crate::l2`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(5.0), generic::l2(&[-3i8, 4]));
+/// ```
+pub fn l2<I>(nums: I) -> Option<f64>
+where
+    I: IntoIterator,
+    I::Item: Numeric,
+{
+    accumulate(crate::RunningL2::default(), nums)
+}
+
+/// L∞ norm; see [`crate::linf`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(128.0), generic::linf(&[-128i8, 4]));
+/// ```
+pub fn linf<I>(nums: I) -> Option<f64>
+where
+    I: IntoIterator,
+    I::Item: Numeric,
+{
+    accumulate(crate::RunningLinf::default(), nums)
+}
+
+/// Lp norm; see [`crate::lp`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(5.0), generic::lp(&[3u16, 4], 2.0));
+/// ```
+pub fn lp<I>(nums: I, p: f64) -> Option<f64>
+where
+    I: IntoIterator,
+    I::Item: Numeric,
+{
+    crate::lp(&to_vec(nums), p)
+}
+
+/// The `k`-th raw moment; see [`crate::moment`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(2.5), generic::moment(&[1u8, 2], 2));
+/// ```
+pub fn moment<I>(nums: I, k: u32) -> Option<f64>
+where
+    I: IntoIterator,
+    I::Item: Numeric,
+{
+    crate::moment(&to_vec(nums), k)
+}
+
+/// The `k`-th central moment; see [`crate::central_moment`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(1.0), generic::central_moment(&[-1i32, 1], 4));
+/// ```
+pub fn central_moment<I>(nums: I, k: u32) -> Option<f64>
+where
+    I: IntoIterator,
+    I::Item: Numeric,
+{
+    crate::central_moment(&to_vec(nums), k)
+}
+
+/// Population skewness; see [`crate::skewness`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(0.0), generic::skewness(1..=3));
+/// ```
+pub fn skewness<I>(nums: I) -> Option<f64>
+where
+    I: IntoIterator,
+    I::Item: Numeric,
+{
+    accumulate(crate::RunningSkewness::default(), nums)
+}
+
+/// Sample skewness; see [`crate::sample_skewness`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, generic::sample_skewness(&[1u64, 2]));
+/// ```
+pub fn sample_skewness<I>(nums: I) -> Option<f64>
+where
+    I: IntoIterator,
+    I::Item: Numeric,
+{
+    accumulate(crate::RunningSkewness::sample(), nums)
+}
+
+/// Population excess kurtosis; see [`crate::kurtosis`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(-2.0), generic::kurtosis(&[-1i8, 1]));
+/// ```
+pub fn kurtosis<I>(nums: I) -> Option<f64>
+where
+    I: IntoIterator,
+    I::Item: Numeric,
+{
+    accumulate(crate::RunningKurtosis::default(), nums)
+}
+
+/// Sample excess kurtosis; see [`crate::sample_kurtosis`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, generic::sample_kurtosis(&[1.0f32, 2.0, 3.0]));
+/// ```
+pub fn sample_kurtosis<I>(nums: I) -> Option<f64>
+where
+    I: IntoIterator,
+    I::Item: Numeric,
+{
+    accumulate(crate::RunningKurtosis::sample(), nums)
+}
+
+/// Median, taking the lower middle value; see
+/// [`crate::median`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(2.0), generic::median(&[3u16, 1, 2, 4]));
+/// ```
+pub fn median<I>(nums: I) -> Option<f64>
+where
+    I: IntoIterator,
+    I::Item: Numeric,
+{
+    median_with(nums, MedianPolicy::Lower)
+}
+
+/// Median with a choice of tie-break; see
+/// [`crate::median_with`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(2.5), generic::median_with(&[3u16, 1, 2, 4], MedianPolicy::Midpoint));
+/// ```
+pub fn median_with<I>(nums: I, policy: MedianPolicy) -> Option<f64>
+where
+    I: IntoIterator,
+    I::Item: Numeric,
+{
+    crate::median_in_place(&mut to_vec(nums), policy)
+}
+
+/// Trimmed mean; see [`crate::trimmed_mean`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(3.0), generic::trimmed_mean(&[1u8, 2, 3, 4, 100], 0.2));
+/// ```
+pub fn trimmed_mean<I>(nums: I, fraction: f64) -> Option<f64>
+where
+    I: IntoIterator,
+    I::Item: Numeric,
+{
+    crate::trimmed_mean(&to_vec(nums), fraction)
+}
+
+/// Winsorized mean; see [`crate::winsorized_mean`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(3.0), generic::winsorized_mean(&[1u8, 2, 3, 4, 100], 0.2));
+/// ```
+pub fn winsorized_mean<I>(nums: I, fraction: f64) -> Option<f64>
+where
+    I: IntoIterator,
+    I::Item: Numeric,
+{
+    crate::winsorized_mean(&to_vec(nums), fraction)
+}
+
+/// Median absolute deviation; see [`crate::mad`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(1.0), generic::mad(&[1i32, 2, 3, 4, 100]));
+/// ```
+pub fn mad<I>(nums: I) -> Option<f64>
+where
+    I: IntoIterator,
+    I::Item: Numeric,
+{
+    crate::mad(&to_vec(nums))
+}
+
+/// Normal-consistent median absolute deviation; see [`crate::normal_mad`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(0.0), generic::normal_mad(&[7u64; 3]));
+/// ```
+pub fn normal_mad<I>(nums: I) -> Option<f64>
+where
+    I: IntoIterator,
+    I::Item: Numeric,
+{
+    crate::normal_mad(&to_vec(nums))
+}
+
+/// Interquartile range; see [`crate::iqr`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(2.0), generic::iqr(1..=5, QuantileMethod::Linear));
+/// ```
+pub fn iqr<I>(nums: I, method: QuantileMethod) -> Option<f64>
+where
+    I: IntoIterator,
+    I::Item: Numeric,
+{
+    crate::iqr(&to_vec(nums), method)
+}
+
+/// Hodges–Lehmann estimator; see [`crate::hodges_lehmann`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(3.0), generic::hodges_lehmann(&[1i16, 2, 3, 4, 100]));
+/// ```
+pub fn hodges_lehmann<I>(nums: I) -> Option<f64>
+where
+    I: IntoIterator,
+    I::Item: Numeric,
+{
+    crate::hodges_lehmann(&to_vec(nums))
+}
+
+/// Huber M-estimator of location; see [`crate::huber_location`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(5.0), generic::huber_location(&[5u8, 5, 6], 1.345));
+/// ```
+pub fn huber_location<I>(nums: I, k: f64) -> Option<f64>
+where
+    I: IntoIterator,
+    I::Item: Numeric,
+{
+    crate::huber_location(&to_vec(nums), k)
+}
+
+/// All the most common values; see [`crate::modes`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(vec![1.0, 3.0], generic::modes(&[3u8, 1, 2, 3, 1]));
+/// ```
+pub fn modes<I>(nums: I) -> Vec<f64>
+where
+    I: IntoIterator,
+    I::Item: Numeric,
+{
+    crate::modes(&to_vec(nums))
+}
+
+/// Mode, taking the smallest of several most common values;
+/// see [`crate::mode`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(7.0), generic::mode(&[7i32, 7, 8]));
+/// ```
+pub fn mode<I>(nums: I) -> Option<f64>
+where
+    I: IntoIterator,
+    I::Item: Numeric,
+{
+    mode_with(nums, ModePolicy::Smallest)
+}
+
+/// Mode with a choice of tie-break; see
+/// [`crate::mode_with`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(3.0), generic::mode_with(&[3u8, 1, 3, 1], ModePolicy::Largest));
+/// ```
+pub fn mode_with<I>(nums: I, policy: ModePolicy) -> Option<f64>
+where
+    I: IntoIterator,
+    I::Item: Numeric,
+{
+    crate::mode_with(&to_vec(nums), policy)
+}
+
+/// The `k`-th smallest value; see [`crate::order_statistic`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(-5.0), generic::order_statistic(&[3i32, -5, 7], 0));
+/// ```
+pub fn order_statistic<I>(nums: I, k: usize) -> Option<f64>
+where
+    I: IntoIterator,
+    I::Item: Numeric,
+{
+    crate::select_in_place(&mut to_vec(nums), k)
+}
+
+/// Quantile; see [`crate::quantile`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(4.6), generic::quantile(1..=5, 0.9, QuantileMethod::Linear));
+/// ```
+pub fn quantile<I>(nums: I, p: f64, method: QuantileMethod) -> Option<f64>
+where
+    I: IntoIterator,
+    I::Item: Numeric,
+{
+    crate::quantile(&to_vec(nums), p, method)
+}
+
+/// Several quantiles; see [`crate::quantiles`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// let qs = generic::quantiles(1u32..=100, &[0.5, 0.99], QuantileMethod::Nearest);
+/// assert_eq!(Some(vec![51.0, 99.0]), qs);
+/// ```
+pub fn quantiles<I>(nums: I, ps: &[f64], method: QuantileMethod) -> Option<Vec<f64>>
+where
+    I: IntoIterator,
+    I::Item: Numeric,
+{
+    crate::quantiles(&to_vec(nums), ps, method)
+}
+
+/// Summary statistics; see [`crate::summary`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(4.0), generic::summary(&[1.0f32, 4.0]).map(|s| s.max));
+/// ```
+pub fn summary<I>(nums: I) -> Option<Summary>
+where
+    I: IntoIterator,
+    I::Item: Numeric,
+{
+    crate::summary(&to_vec(nums))
+}
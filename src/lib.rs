@@ -3,15 +3,73 @@
 // Please see the file LICENSE in the source
 // distribution of this software for license terms.
 
-///! Functions to compute various statistics on a slice of
-///! floating-point numbers.
+//! Functions to compute various statistics on a slice of
+//! floating-point numbers.
+//!
+//! NaN values in the input propagate into the result; see
+//! [`NanPolicy`] for alternatives. The [`checked`] module
+//! has versions of the statistics that report why a result
+//! is undefined, and the [`generic`] module has versions
+//! that take integers, `f32`s and iterators.
+
+mod accumulator;
+pub use accumulator::*;
+mod anova;
+pub use anova::*;
+pub mod checked;
+mod contingency;
+pub use contingency::*;
+mod correlation;
+pub use correlation::*;
+mod curve;
+pub use curve::*;
+mod distance;
+pub use distance::*;
+pub mod distributions;
+mod error;
+pub use error::*;
+pub mod generic;
+pub use generic::Numeric;
+mod means;
+pub use means::*;
+mod mode;
+pub use mode::*;
+mod moments;
+pub use moments::*;
+mod nan;
+pub use nan::*;
+mod nonparametric;
+pub use nonparametric::*;
+mod normality;
+pub use normality::*;
+mod quantile;
+pub use quantile::*;
+mod rank;
+pub use rank::*;
+mod regression;
+pub use regression::*;
+mod robust;
+pub use robust::*;
+mod select;
+pub use select::*;
+mod special;
+mod summary;
+pub use summary::*;
+mod ttest;
+pub use ttest::*;
+mod weighted;
+pub use weighted::*;
+
+use accumulator::accumulate;
 
 /// Type of statistics function. If the statistic
 /// is ill-defined, `None` will be returned.
 pub type StatFn = fn(&[f64]) -> Option<f64>;
 
 /// Arithmetic mean of input values. The mean of an empty
-/// list is 0.0.
+/// list is 0.0. The values are summed with Neumaier's
+/// compensated summation, so that large offsets and long
+/// inputs do not lose precision.
 ///
 /// # Examples:
 ///
@@ -28,20 +86,14 @@ pub type StatFn = fn(&[f64]) -> Option<f64>;
 /// assert_eq!(Some(1.0), mean(&[1.0]));
 /// ```
 pub fn mean(nums: &[f64]) -> Option<f64> {
-    if nums.is_empty() {
-        Some(0.0)
-    } else {
-        let len = nums.len() as f64;
-        let mut sum = 0.0;
-        for i in &nums[..] {
-            sum = sum + i;
-        }
-        Some(sum / len)
-    }
+    accumulate(RunningMean::default(), nums)
 }
 
 /// Population standard deviation of input values. The
-/// standard deviation of an empty list is undefined.
+/// standard deviation of an empty list is undefined. The
+/// variance is computed in one pass with Welford's update,
+/// which avoids the cancellation of the textbook
+/// sum-of-squares formula.
 ///
 /// # Examples:
 ///
@@ -58,23 +110,94 @@ pub fn mean(nums: &[f64]) -> Option<f64> {
 /// assert_eq!(Some(0.0), stddev(&[1.0]));
 /// ```
 pub fn stddev(nums: &[f64]) -> Option<f64> {
-    if nums.is_empty() {
-        None
-    } else if nums.len() == 1 {
-        Some(0.0)
-    } else {
-        let avg = mean(nums).unwrap();
-        let mut sums = Vec::new();
-        for i in &nums[..] {
-            sums.push((i - avg).powf(2.0));
-        }
-        Some(mean(&sums[..]).unwrap().sqrt())
-    }
+    accumulate(RunningStddev::default(), nums)
+}
+
+/// Population variance of input values: the mean squared
+/// deviation from the mean. The variance of an empty list is
+/// undefined. Computed the same way as [`stddev`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, variance(&[]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(4.0), variance(&[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(0.0), variance(&[1.0]));
+/// ```
+pub fn variance(nums: &[f64]) -> Option<f64> {
+    accumulate(RunningVariance::default(), nums)
+}
+
+/// Sample variance of input values, with Bessel's
+/// correction: the sum of squared deviations divided by
+/// one less than the number of values. The sample variance
+/// of fewer than two values is undefined.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, sample_variance(&[1.0]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(2.0), sample_variance(&[1.0, 3.0]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(0.0), sample_variance(&[5.0, 5.0, 5.0]));
+/// ```
+pub fn sample_variance(nums: &[f64]) -> Option<f64> {
+    accumulate(RunningVariance::sample(), nums)
+}
+
+/// Sample standard deviation of input values: the square
+/// root of [`sample_variance`]. The sample standard deviation
+/// of fewer than two values is undefined.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, sample_stddev(&[]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(2.0), sample_stddev(&[1.0, 3.0, 5.0]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, sample_stddev(&[1.0]));
+/// ```
+pub fn sample_stddev(nums: &[f64]) -> Option<f64> {
+    sample_variance(nums).map(f64::sqrt)
+}
+
+/// How to pick the median of an even number of values,
+/// which has two middle values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MedianPolicy {
+    /// The lower middle value.
+    Lower,
+    /// The upper middle value.
+    Upper,
+    /// The average of the two middle values. This is the
+    /// conventional median, as computed by spreadsheets and
+    /// numpy.
+    Midpoint,
 }
 
 /// Median value of input values, taking the value closer
 /// to the beginning to break ties. The median
-/// of an empty list is undefined.
+/// of an empty list is undefined. This is
+/// [`median_with`] using [`MedianPolicy::Lower`].
 ///
 /// # Examples:
 ///
@@ -91,26 +214,114 @@ pub fn stddev(nums: &[f64]) -> Option<f64> {
 /// assert_eq!(Some(5.0), median(&[5.0]));
 /// ```
 pub fn median(nums: &[f64]) -> Option<f64> {
-    // Make a sorted copy of the input floats.
-    let mut nums = nums.to_owned();
-    // https://users.rust-lang.org/t/how-to-sort-a-vec-of-floats/2838/2
-    nums.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    median_with(nums, MedianPolicy::Lower)
+}
+
+/// Median value of input values, using `policy` to choose
+/// between the two middle values of an even-length
+/// list. The median of an empty list is undefined. This is
+/// [`median_in_place`] on a copy of the input.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// let nums = [0.0, 0.5, -1.0, 1.0];
+/// assert_eq!(Some(0.0), median_with(&nums, MedianPolicy::Lower));
+/// assert_eq!(Some(0.5), median_with(&nums, MedianPolicy::Upper));
+/// assert_eq!(Some(0.25), median_with(&nums, MedianPolicy::Midpoint));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(2.0), median_with(&[3.0, 1.0, 2.0], MedianPolicy::Midpoint));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, median_with(&[], MedianPolicy::Upper));
+/// ```
+pub fn median_with(nums: &[f64], policy: MedianPolicy) -> Option<f64> {
+    median_in_place(&mut nums.to_owned(), policy)
+}
 
-    if nums.is_empty() {
-        None
+/// Median value of input values, found in place by
+/// selection in expected linear time rather than by
+/// sorting. The values are left reordered. Ties are broken
+/// as for [`median_with`]. The median of values including a
+/// NaN is NaN.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// let mut nums = [0.0, 0.5, -1.0, 1.0];
+/// assert_eq!(Some(0.25), median_in_place(&mut nums, MedianPolicy::Midpoint));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(3.0), median_in_place(&mut [4.0, 3.0, 1.0, 5.0], MedianPolicy::Lower));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, median_in_place(&mut [], MedianPolicy::Midpoint));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert!(median_in_place(&mut [1.0, f64::NAN], MedianPolicy::Upper).unwrap().is_nan());
+/// ```
+pub fn median_in_place(nums: &mut [f64], policy: MedianPolicy) -> Option<f64> {
+    let n = nums.len();
+    let lower = select_in_place(nums, n.checked_sub(1)? / 2)?;
+    if n % 2 == 1 || policy == MedianPolicy::Lower || lower.is_nan() {
+        return Some(lower);
+    }
+    // Selection leaves the upper middle value as the
+    // smallest of the values after the lower one.
+    let upper = nums[n / 2..].iter().copied().fold(f64::INFINITY, f64::min);
+    match policy {
+        MedianPolicy::Upper => Some(upper),
+        _ => Some(midpoint(lower, upper)),
+    }
+}
+
+/// Average of two values, without overflowing when both are
+/// huge.
+fn midpoint(a: f64, b: f64) -> f64 {
+    let mid = (a + b) / 2.0;
+    if mid.is_finite() {
+        mid
     } else {
-        let mid = (nums.len() - 1) / 2;
-        Some(nums[mid])
+        a / 2.0 + b / 2.0
     }
 }
 
-/// L2 norm (Euclidean norm) of input values. The L2
+/// L1 norm (Manhattan norm) of input values: the sum of
+/// their absolute values, with compensated summation. The L1
 /// norm of an empty list is 0.0.
 ///
 /// # Examples:
 ///
 /// ```
 /// # use stats::*;
+/// assert_eq!(Some(0.0), l1(&[]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(7.0), l1(&[-3.0, 4.0]));
+/// ```
+pub fn l1(nums: &[f64]) -> Option<f64> {
+    accumulate(RunningL1::default(), nums)
+}
+
+/// L2 norm (Euclidean norm) of input values. The L2
+/// norm of an empty list is 0.0. The squares are summed
+/// relative to the largest magnitude seen so far, as in the
+/// BLAS `nrm2`, so that the result neither overflows nor
+/// underflows unless the norm itself does.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
 /// assert_eq!(Some(0.0), l2(&[]));
 /// ```
 /// ```
@@ -121,14 +332,75 @@ pub fn median(nums: &[f64]) -> Option<f64> {
 /// # use stats::*;
 /// assert_eq!(Some(5.0), l2(&[5.0]));
 /// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(5e300), l2(&[3e300, 4e300]));
+/// assert_eq!(Some(5e-300), l2(&[3e-300, 4e-300]));
+/// ```
 pub fn l2(nums: &[f64]) -> Option<f64> {
-    if nums.is_empty() {
-        Some(0.0)
-    } else {
-        let mut sum = 0.0;
-        for i in &nums[..] {
-            sum = sum + i.powf(2.0);
-        }
-        Some(sum.sqrt())
+    accumulate(RunningL2::default(), nums)
+}
+
+/// L∞ norm (maximum norm) of input values: the largest
+/// absolute value. The L∞ norm of an empty list is 0.0.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(0.0), linf(&[]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(4.0), linf(&[-4.0, 3.0]));
+/// ```
+pub fn linf(nums: &[f64]) -> Option<f64> {
+    accumulate(RunningLinf::default(), nums)
+}
+
+/// Lp norm of input values: the `p`-th root of the sum of
+/// the `p`-th powers of their absolute values. The values
+/// are scaled by the largest magnitude before taking powers,
+/// so that the result neither overflows nor underflows
+/// unless the norm itself does. The Lp norm of an empty list
+/// is 0.0. It is undefined unless `p` is at least 1, but may
+/// be infinite, giving [`linf`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// let nums = [-3.0, 4.0];
+/// assert_eq!(l1(&nums), lp(&nums, 1.0));
+/// assert_eq!(l2(&nums), lp(&nums, 2.0));
+/// assert_eq!(linf(&nums), lp(&nums, f64::INFINITY));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert!((lp(&[1.0, 2.0], 3.0).unwrap() - 9.0f64.cbrt()).abs() < 1e-12);
+/// assert!((lp(&[1e300, 2e300], 3.0).unwrap() / 1e300 - 9.0f64.cbrt()).abs() < 1e-12);
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, lp(&[1.0], 0.5));
+/// ```
+pub fn lp(nums: &[f64], p: f64) -> Option<f64> {
+    if p.is_nan() || p < 1.0 {
+        return None;
+    }
+    if p == 1.0 {
+        return l1(nums);
+    }
+    if p == 2.0 {
+        return l2(nums);
+    }
+    let scale = linf(nums)?;
+    if p.is_infinite() || scale == 0.0 || !scale.is_finite() {
+        return Some(scale);
+    }
+    let mut sum = accumulator::CompensatedSum::default();
+    for &x in nums {
+        sum.add((x.abs() / scale).powf(p));
     }
+    Some(scale * sum.value().powf(p.recip()))
 }
@@ -3,56 +3,1302 @@
 // Please see the file LICENSE in the source
 // distribution of this software for license terms.
 
+//! Compute statistics on numbers presented one-per-line on
+//! standard input, or on pairs of numbers presented as two
+//! columns separated by whitespace or a comma, or on rows
+//! of several numbers for a multiple regression. The input
+//! may instead be read from a file, or from one file per
+//! column, as for the two samples of a t-test.
+//! Alternatively, print a frequency table of the input
+//! lines taken as strings.
+//!
+//! The exit status tells what went wrong, if anything:
+//!
+//! * 0: success
+//! * 1: bad command-line arguments
+//! * 2: error reading the input
+//! * 3: input line that is not a number
+//! * 4: empty input, for a statistic that needs values
+//! * 5: NaN in the input, with `--nan=error`
+//! * 6: not enough input values for a statistic
+//! * 7: invalid parameter for a statistic
+//! * 8: any other failure to compute a statistic
+//! * 9: all input values equal, where spread is needed
+//! * 10: negative input value, where none are allowed
+//! * 11: paired inputs of different lengths
+//! * 12: all-zero vector, where a direction is needed
+//! * 13: several modes, with `--mode-policy=unique`
+//! * 14: collinear predictors in a regression
+//! * 15: zero or negative value, where a log is taken
+//! * 16: all-zero row, column or category of counts
+
+use std::io::BufRead;
 use std::process::exit;
 
-///! Compute a statistic on numbers presented one-per-line on
-///! standard input.
+use stats::checked::{self, TryStatFn};
+use stats::{Accumulator, StatsError};
+
+/// Constructor for a fresh accumulator, for statistics that
+/// can be computed without holding the whole input.
+type NewAccFn = fn() -> Box<dyn Accumulator>;
+
+/// Exit status for a statistic that could not be computed.
+fn exit_code(error: &StatsError) -> i32 {
+    match error {
+        StatsError::EmptyInput => 4,
+        StatsError::ContainsNan => 5,
+        StatsError::NotEnoughSamples { .. } => 6,
+        StatsError::InvalidParameter(_) => 7,
+        StatsError::ZeroVariance => 9,
+        StatsError::NegativeValue => 10,
+        StatsError::LengthMismatch => 11,
+        StatsError::ZeroNorm => 12,
+        StatsError::Multimodal => 13,
+        StatsError::Collinear => 14,
+        StatsError::NonPositiveValue => 15,
+        StatsError::EmptyCategory => 16,
+        _ => 8,
+    }
+}
 
 /// Report proper usage and exit.
 fn usage() -> ! {
-    eprintln!("stats: usage: stats [--mean|--stddev|--median|--l2]");
+    eprintln!(
+        "stats: usage: stats [--nan=propagate|skip|error] \
+         [--median-policy=lower|upper|midpoint] [--mode-policy=smallest|largest|unique] \
+         [--quantile-method=METHOD] [--weights=frequency|reliability] \
+         [--confidence=L] STAT... [FILE...]\n\
+         where STAT is one of \
+         --mean|--geometric-mean|--harmonic-mean|--power-mean P\
+         |--trimmed-mean F|--winsorized-mean F|--huber K\
+         |--stddev|--variance|--sample-stddev|--sample-variance|--median\
+         |--mode|--mad|--normal-mad|--iqr|--hodges-lehmann\
+         |--l1|--l2|--linf|--lp P\
+         |--skewness|--sample-skewness|--kurtosis|--sample-kurtosis\
+         |--moment K|--central-moment K\
+         |--quantile P|--percentiles P,P,...|--summary|--t-test MU|--normality\n\
+         or, on two-column input, one of \
+         --dot|--euclidean|--manhattan|--cosine\
+         |--weighted-mean|--weighted-variance|--weighted-sample-variance\
+         |--weighted-median|--weighted-quantile P\
+         |--covariance|--sample-covariance|--pearson|--spearman|--kendall\
+         |--regression|--polynomial-fit D|--exponential-fit|--power-fit\
+         |--paired-t-test|--welch-t-test|--mann-whitney|--wilcoxon|--kolmogorov-smirnov\
+         |--goodness-of-fit\n\
+         or, on a table of counts, --independence|--fisher\n\
+         or, on lines of a label and a value, or one FILE per group, \
+         --anova|--tukey|--kruskal-wallis\n\
+         or, on K predictor columns followed by a response column, \
+         --multiple-regression K\n\
+         with --predict X,X,... to print the fitted curves at each X\n\
+         reading one FILE in place of stdin, or one FILE per column\n\
+         or: stats --frequencies [FILE]"
+    );
     exit(1);
 }
 
-/// Do the computation.
-fn main() {
-    // Process the argument.
-    let args: Vec<String> = std::env::args().collect();
-    if args.len() != 2 {
-        usage();
+/// If `arg` is the option `--name`, return its value: either
+/// the part after `=`, or else the next argument.
+fn option_value(arg: &str, name: &str, rest: &mut impl Iterator<Item = String>) -> Option<String> {
+    let value = arg.strip_prefix(name)?;
+    if value.is_empty() {
+        Some(rest.next().unwrap_or_else(|| usage()))
+    } else {
+        value.strip_prefix('=').map(str::to_owned)
     }
-    let target = &args[1];
-    let argdescs: &[(&str, stats::StatFn)] = &[
-        ("--mean", stats::mean),
-        ("--stddev", stats::stddev),
-        ("--median", stats::median),
-        ("--l2", stats::l2),
-    ];
-    let stat = argdescs
+}
+
+/// Look up the value `name` in a table of choices for an
+/// option.
+fn choice<T: Copy>(choices: &[(&str, T)], name: &str) -> T {
+    choices
         .iter()
-        .find(|(a, _)| a == target)
+        .find(|(n, _)| *n == name)
         .unwrap_or_else(|| usage())
-        .1;
+        .1
+}
+
+/// Parse a probability given as a quantile (0 to 1) or
+/// percentile (0 to 100), returning it as a probability.
+fn probability(arg: &str, scale: f64) -> f64 {
+    let p = arg.trim().parse::<f64>().unwrap_or_else(|_| usage()) / scale;
+    if !(0.0..=1.0).contains(&p) {
+        usage();
+    }
+    p
+}
+
+/// Parse the order of a moment.
+fn order(arg: &str) -> u32 {
+    arg.trim().parse().unwrap_or_else(|_| usage())
+}
+
+/// Parse the numeric parameter of a statistic.
+fn parameter(arg: &str) -> f64 {
+    arg.trim().parse().unwrap_or_else(|_| usage())
+}
+
+/// Make a boxed default accumulator of the given type.
+fn streaming<A: Accumulator + Default + 'static>() -> Box<dyn Accumulator> {
+    Box::new(A::default())
+}
+
+/// Statistics selected by a plain flag.
+const ARGDESCS: &[(&str, TryStatFn, Option<NewAccFn>)] = &[
+    (
+        "--mean",
+        checked::mean,
+        Some(streaming::<stats::RunningMean>),
+    ),
+    (
+        "--geometric-mean",
+        checked::geometric_mean,
+        Some(streaming::<stats::RunningGeometricMean>),
+    ),
+    (
+        "--harmonic-mean",
+        checked::harmonic_mean,
+        Some(streaming::<stats::RunningHarmonicMean>),
+    ),
+    (
+        "--stddev",
+        checked::stddev,
+        Some(streaming::<stats::RunningStddev>),
+    ),
+    (
+        "--variance",
+        checked::variance,
+        Some(streaming::<stats::RunningVariance>),
+    ),
+    (
+        "--sample-stddev",
+        checked::sample_stddev,
+        Some(|| Box::new(stats::RunningStddev::sample())),
+    ),
+    (
+        "--sample-variance",
+        checked::sample_variance,
+        Some(|| Box::new(stats::RunningVariance::sample())),
+    ),
+    (
+        "--skewness",
+        checked::skewness,
+        Some(streaming::<stats::RunningSkewness>),
+    ),
+    (
+        "--sample-skewness",
+        checked::sample_skewness,
+        Some(|| Box::new(stats::RunningSkewness::sample())),
+    ),
+    (
+        "--kurtosis",
+        checked::kurtosis,
+        Some(streaming::<stats::RunningKurtosis>),
+    ),
+    (
+        "--sample-kurtosis",
+        checked::sample_kurtosis,
+        Some(|| Box::new(stats::RunningKurtosis::sample())),
+    ),
+    ("--mad", checked::mad, None),
+    ("--normal-mad", checked::normal_mad, None),
+    ("--hodges-lehmann", checked::hodges_lehmann, None),
+    ("--l1", checked::l1, Some(streaming::<stats::RunningL1>)),
+    ("--l2", checked::l2, Some(streaming::<stats::RunningL2>)),
+    (
+        "--linf",
+        checked::linf,
+        Some(streaming::<stats::RunningLinf>),
+    ),
+];
+
+/// Type of checked statistics function with a numeric
+/// parameter.
+type TryParamFn = fn(&[f64], f64) -> Result<f64, StatsError>;
+
+/// Statistics selected by a flag with a numeric parameter.
+const PARAM_ARGDESCS: &[(&str, TryParamFn)] = &[
+    ("--power-mean", checked::power_mean),
+    ("--trimmed-mean", checked::trimmed_mean),
+    ("--winsorized-mean", checked::winsorized_mean),
+    ("--huber", checked::huber_location),
+    ("--lp", checked::lp),
+];
+
+/// Type of checked statistics function of two columns.
+type TryPairFn = fn(&[f64], &[f64]) -> Result<f64, StatsError>;
+
+/// Statistics of two-column input selected by a plain flag.
+const PAIR_ARGDESCS: &[(&str, TryPairFn)] = &[
+    ("--dot", checked::dot),
+    ("--euclidean", checked::euclidean_distance),
+    ("--manhattan", checked::manhattan_distance),
+    ("--cosine", checked::cosine_distance),
+    ("--weighted-mean", checked::weighted_mean),
+    ("--weighted-variance", checked::weighted_variance),
+    ("--weighted-median", checked::weighted_median),
+    ("--covariance", checked::covariance),
+    ("--sample-covariance", checked::sample_covariance),
+];
+
+/// Type of checked correlation function of two columns.
+type TryCorrelationFn = fn(&[f64], &[f64]) -> Result<stats::Correlation, StatsError>;
+
+/// Correlation coefficients of two-column input, each
+/// reported with its p-value.
+const CORRELATION_ARGDESCS: &[(&str, TryCorrelationFn)] = &[
+    ("--pearson", checked::pearson),
+    ("--spearman", checked::spearman),
+    ("--kendall", checked::kendall_tau_b),
+];
+
+/// Which t-test to run.
+#[derive(Clone, Copy)]
+enum TTestKind {
+    /// One-sample test against the given mean.
+    OneSample(f64),
+    /// Paired test of two columns.
+    Paired,
+    /// Welch's test of two independent samples.
+    Welch,
+}
+
+/// Type of checked hypothesis test of two samples.
+type TryTestFn = fn(&[f64], &[f64]) -> Result<stats::TestResult, StatsError>;
+
+/// Tests comparing two samples, or counts with the expected
+/// proportions, each reported with its p-value.
+const TEST_ARGDESCS: &[(&str, TryTestFn)] = &[
+    ("--goodness-of-fit", checked::chi_squared_goodness_of_fit),
+    ("--mann-whitney", checked::mann_whitney_u),
+    ("--wilcoxon", checked::wilcoxon_signed_rank),
+    (
+        "--kolmogorov-smirnov",
+        checked::kolmogorov_smirnov_two_sample,
+    ),
+];
+
+/// Type of checked test of normality.
+type TryNormalityFn = fn(&[f64]) -> Result<stats::TestResult, StatsError>;
+
+/// A statistic requested on the command line. Options that
+/// affect statistics may follow the statistic flags, so the
+/// requests are only turned into [`Stat`]s once all the
+/// arguments have been read.
+enum Request {
+    /// A statistic from `ARGDESCS`.
+    Plain(&'static str, TryStatFn, Option<NewAccFn>),
+    /// A statistic from `PARAM_ARGDESCS`, with its parameter
+    /// as given and as parsed.
+    Parameterized(&'static str, String, TryParamFn, f64),
+    /// A statistic from `PAIR_ARGDESCS`.
+    Pair(&'static str, TryPairFn),
+    /// A correlation from `CORRELATION_ARGDESCS`.
+    Correlation(&'static str, TryCorrelationFn),
+    /// A test from `TEST_ARGDESCS`.
+    Test(&'static str, TryTestFn),
+    /// The tests of normality.
+    Normality,
+    /// The chi-squared test of independence of a contingency
+    /// table, with Cramér's V.
+    Independence,
+    /// Fisher's exact test of a 2×2 contingency table.
+    Fisher,
+    /// One-way analysis of variance of groups.
+    Anova,
+    /// Tukey's comparisons of each pair of groups.
+    Tukey,
+    /// The Kruskal-Wallis test of groups.
+    KruskalWallis,
+    /// The weighted sample variance.
+    WeightedSampleVariance,
+    /// Weighted quantiles, with their labels.
+    WeightedQuantiles(Vec<(String, f64)>),
+    /// Linear regression of the last column on the given
+    /// number of predictor columns before it.
+    Regression(usize),
+    /// A curve fitted to two-column input, with the degree
+    /// of a polynomial.
+    Fit(stats::CurveKind, usize),
+    /// A t-test.
+    TTest(TTestKind),
+    /// The median.
+    Median,
+    /// The mode.
+    Mode,
+    /// The interquartile range.
+    Iqr,
+    /// A raw moment of the given order, or a central moment
+    /// if the flag says so.
+    Moment(bool, u32),
+    /// Quantiles, with their labels.
+    Quantiles(Vec<(String, f64)>),
+    /// The summary report.
+    Summary,
+}
+
+/// Options that affect how statistics are computed.
+#[derive(Clone, Copy)]
+struct Settings {
+    median_policy: stats::MedianPolicy,
+    mode_policy: stats::ModePolicy,
+    nan_policy: stats::NanPolicy,
+    quantile_method: stats::QuantileMethod,
+    weight_kind: stats::WeightKind,
+    confidence: f64,
+}
+
+/// Number of columns of a statistic of a contingency table,
+/// which takes as many as the first line of input has.
+const TABLE: usize = 0;
+
+/// Number of columns of a statistic of groups, which takes
+/// one column per group.
+const GROUPS: usize = usize::MAX;
+
+/// Function computing one or more values from the columns
+/// of the whole input.
+type BatchFn = Box<dyn Fn(&[Vec<f64>]) -> Vec<Result<f64, StatsError>>>;
+
+/// A statistic to be computed, reporting one or more values.
+struct Stat {
+    /// Name of the statistic in error messages.
+    name: String,
+    /// Names of the values.
+    labels: Vec<String>,
+    /// Number of input columns.
+    columns: usize,
+    /// Compute the values from the whole input.
+    batch: BatchFn,
+    /// Make an accumulator that computes the (single) value
+    /// incrementally, if the statistic allows it.
+    streaming: Option<NewAccFn>,
+}
+
+impl Request {
+    /// Whether this request compares groups.
+    fn grouped(&self) -> bool {
+        matches!(
+            self,
+            Request::Anova | Request::Tukey | Request::KruskalWallis
+        )
+    }
+
+    /// Whether this request compares independent samples,
+    /// which may differ in length.
+    fn independent(&self) -> bool {
+        match self {
+            Request::TTest(TTestKind::Welch) => true,
+            Request::Test(flag, _) => matches!(*flag, "--mann-whitney" | "--kolmogorov-smirnov"),
+            _ => false,
+        }
+    }
+
+    /// The statistic for this request, given the names of
+    /// the groups for a comparison of groups.
+    fn stat(self, settings: Settings, predictions: &[(String, f64)], groups: &[String]) -> Stat {
+        match self {
+            Request::Plain(flag, stat, streaming) => Stat {
+                name: flag.trim_start_matches('-').to_owned(),
+                labels: vec![flag.trim_start_matches('-').to_owned()],
+                columns: 1,
+                batch: Box::new(move |cols| vec![stat(&cols[0])]),
+                streaming,
+            },
+            Request::Parameterized(flag, value, stat, p) => Stat {
+                name: flag.trim_start_matches('-').to_owned(),
+                labels: vec![format!("{}{}", flag.trim_start_matches('-'), value)],
+                columns: 1,
+                batch: Box::new(move |cols| vec![stat(&cols[0], p)]),
+                streaming: None,
+            },
+            Request::Pair(flag, stat) => Stat {
+                name: flag.trim_start_matches('-').to_owned(),
+                labels: vec![flag.trim_start_matches('-').to_owned()],
+                columns: 2,
+                batch: Box::new(move |cols| vec![stat(&cols[0], &cols[1])]),
+                streaming: None,
+            },
+            Request::Correlation(flag, stat) => {
+                let name = flag.trim_start_matches('-');
+                Stat {
+                    name: name.to_owned(),
+                    labels: vec![name.to_owned(), format!("{}-p", name)],
+                    columns: 2,
+                    batch: Box::new(move |cols| match stat(&cols[0], &cols[1]) {
+                        Ok(r) => vec![Ok(r.coefficient), Ok(r.p_value)],
+                        Err(e) => vec![Err(e); 2],
+                    }),
+                    streaming: None,
+                }
+            }
+            Request::Test(flag, test) => {
+                let name = flag.trim_start_matches('-');
+                Stat {
+                    name: name.to_owned(),
+                    labels: vec![name.to_owned(), format!("{}-p", name)],
+                    columns: 2,
+                    batch: Box::new(move |cols| match test(&cols[0], &cols[1]) {
+                        Ok(r) => vec![Ok(r.statistic), Ok(r.p_value)],
+                        Err(e) => vec![Err(e); 2],
+                    }),
+                    streaming: None,
+                }
+            }
+            Request::Normality => {
+                let tests: [(&str, TryNormalityFn); 3] = [
+                    ("shapiro-wilk", checked::shapiro_wilk),
+                    ("anderson-darling", checked::anderson_darling),
+                    ("jarque-bera", checked::jarque_bera),
+                ];
+                Stat {
+                    name: "normality".to_owned(),
+                    labels: tests
+                        .iter()
+                        .flat_map(|(name, _)| vec![name.to_string(), format!("{}-p", name)])
+                        .collect(),
+                    columns: 1,
+                    batch: Box::new(move |cols| {
+                        tests
+                            .iter()
+                            .flat_map(|(_, test)| match test(&cols[0]) {
+                                Ok(r) => vec![Ok(r.statistic), Ok(r.p_value)],
+                                Err(e) => vec![Err(e); 2],
+                            })
+                            .collect()
+                    }),
+                    streaming: None,
+                }
+            }
+            Request::Independence => Stat {
+                name: "independence".to_owned(),
+                labels: vec![
+                    "chi-squared".to_owned(),
+                    "chi-squared-p".to_owned(),
+                    "cramers-v".to_owned(),
+                ],
+                columns: TABLE,
+                batch: Box::new(|cols| {
+                    // The tests are the same for the table and
+                    // its transpose, so the columns serve as
+                    // rows.
+                    let table: Vec<&[f64]> = cols.iter().map(Vec::as_slice).collect();
+                    match checked::chi_squared_independence(&table) {
+                        Ok(r) => vec![Ok(r.statistic), Ok(r.p_value), checked::cramers_v(&table)],
+                        Err(e) => vec![Err(e); 3],
+                    }
+                }),
+                streaming: None,
+            },
+            Request::Fisher => Stat {
+                name: "fisher".to_owned(),
+                labels: vec!["fisher-odds-ratio".to_owned(), "fisher-p".to_owned()],
+                columns: TABLE,
+                batch: Box::new(|cols| {
+                    let table: Vec<&[f64]> = cols.iter().map(Vec::as_slice).collect();
+                    match checked::fisher_exact(&table) {
+                        Ok(r) => vec![Ok(r.statistic), Ok(r.p_value)],
+                        Err(e) => vec![Err(e); 2],
+                    }
+                }),
+                streaming: None,
+            },
+            Request::Anova => Stat {
+                name: "anova".to_owned(),
+                labels: ["f", "df-between", "df-within", "p", "eta-squared"]
+                    .iter()
+                    .map(|label| format!("anova-{}", label))
+                    .collect(),
+                columns: GROUPS,
+                batch: Box::new(|cols| {
+                    let groups: Vec<&[f64]> = cols.iter().map(Vec::as_slice).collect();
+                    match checked::one_way_anova(&groups) {
+                        Ok(r) => vec![
+                            Ok(r.statistic),
+                            Ok(r.between_degrees_of_freedom),
+                            Ok(r.within_degrees_of_freedom),
+                            Ok(r.p_value),
+                            Ok(r.eta_squared),
+                        ],
+                        Err(e) => vec![Err(e); 5],
+                    }
+                }),
+                streaming: None,
+            },
+            Request::Tukey => {
+                // Each pair is labelled by its difference, as
+                // the second group less the first.
+                let mut labels = Vec::new();
+                for (i, first) in groups.iter().enumerate() {
+                    for second in &groups[i + 1..] {
+                        labels.extend(
+                            ["", "-lower", "-upper", "-p"]
+                                .iter()
+                                .map(|suffix| format!("tukey-{}-{}{}", second, first, suffix)),
+                        );
+                    }
+                }
+                if labels.is_empty() {
+                    labels.push("tukey".to_owned());
+                }
+                let n = labels.len();
+                Stat {
+                    name: "tukey".to_owned(),
+                    labels,
+                    columns: GROUPS,
+                    batch: Box::new(move |cols| {
+                        let groups: Vec<&[f64]> = cols.iter().map(Vec::as_slice).collect();
+                        match checked::tukey_hsd(&groups, settings.confidence) {
+                            Ok(comparisons) => comparisons
+                                .iter()
+                                .flat_map(|c| [c.difference, c.lower, c.upper, c.p_value])
+                                .map(Ok)
+                                .collect(),
+                            Err(e) => vec![Err(e); n],
+                        }
+                    }),
+                    streaming: None,
+                }
+            }
+            Request::KruskalWallis => Stat {
+                name: "kruskal-wallis".to_owned(),
+                labels: vec!["kruskal-wallis".to_owned(), "kruskal-wallis-p".to_owned()],
+                columns: GROUPS,
+                batch: Box::new(|cols| {
+                    let groups: Vec<&[f64]> = cols.iter().map(Vec::as_slice).collect();
+                    match checked::kruskal_wallis(&groups) {
+                        Ok(r) => vec![Ok(r.statistic), Ok(r.p_value)],
+                        Err(e) => vec![Err(e); 2],
+                    }
+                }),
+                streaming: None,
+            },
+            Request::Regression(k) => {
+                let mut names = vec!["intercept".to_owned()];
+                if k == 1 {
+                    names.push("slope".to_owned());
+                } else {
+                    names.extend((1..=k).map(|i| format!("b{}", i)));
+                }
+                let mut labels = Vec::new();
+                for name in &names {
+                    labels.push(name.clone());
+                    for suffix in ["se", "t", "p"] {
+                        labels.push(format!("{}-{}", name, suffix));
+                    }
+                }
+                for label in ["r-squared", "adjusted-r-squared", "residual-se", "df"] {
+                    labels.push(label.to_owned());
+                }
+                let n = labels.len();
+                Stat {
+                    name: "regression".to_owned(),
+                    labels,
+                    columns: k + 1,
+                    batch: Box::new(move |cols| {
+                        let predictors: Vec<&[f64]> = cols[..k].iter().map(Vec::as_slice).collect();
+                        match checked::multiple_regression(&predictors, &cols[k]) {
+                            Ok(fit) => {
+                                let mut values = Vec::new();
+                                for i in 0..=k {
+                                    values.push(fit.coefficients[i]);
+                                    values.push(fit.standard_errors[i]);
+                                    values.push(fit.t_statistics[i]);
+                                    values.push(fit.p_values[i]);
+                                }
+                                values.push(fit.r_squared);
+                                values.push(fit.adjusted_r_squared);
+                                values.push(fit.residual_standard_error);
+                                values.push(fit.degrees_of_freedom as f64);
+                                values.into_iter().map(Ok).collect()
+                            }
+                            Err(e) => vec![Err(e); n],
+                        }
+                    }),
+                    streaming: None,
+                }
+            }
+            Request::Fit(kind, degree) => {
+                let (name, mut labels) = match kind {
+                    stats::CurveKind::Polynomial => (
+                        "polynomial",
+                        (0..=degree).map(|i| format!("c{}", i)).collect(),
+                    ),
+                    stats::CurveKind::Exponential => {
+                        ("exponential", vec!["a".to_owned(), "b".to_owned()])
+                    }
+                    stats::CurveKind::PowerLaw => ("power", vec!["a".to_owned(), "b".to_owned()]),
+                };
+                for label in ["r-squared", "adjusted-r-squared", "residual-se"] {
+                    labels.push(label.to_owned());
+                }
+                labels.extend(predictions.iter().map(|(x, _)| format!("y({})", x)));
+                let labels: Vec<String> = labels
+                    .into_iter()
+                    .map(|label| format!("{}-{}", name, label))
+                    .collect();
+                let n = labels.len();
+                let xs: Vec<f64> = predictions.iter().map(|&(_, x)| x).collect();
+                Stat {
+                    name: format!("{}-fit", name),
+                    labels,
+                    columns: 2,
+                    batch: Box::new(move |cols| {
+                        let fit = match kind {
+                            stats::CurveKind::Polynomial => {
+                                checked::polynomial_fit(&cols[0], &cols[1], degree)
+                            }
+                            stats::CurveKind::Exponential => {
+                                checked::exponential_fit(&cols[0], &cols[1])
+                            }
+                            stats::CurveKind::PowerLaw => {
+                                checked::power_law_fit(&cols[0], &cols[1])
+                            }
+                        };
+                        match fit {
+                            Ok(fit) => {
+                                let mut values = fit.parameters.clone();
+                                values.push(fit.regression.r_squared);
+                                values.push(fit.regression.adjusted_r_squared);
+                                values.push(fit.regression.residual_standard_error);
+                                values.extend(xs.iter().map(|&x| fit.predict(x)));
+                                values.into_iter().map(Ok).collect()
+                            }
+                            Err(e) => vec![Err(e); n],
+                        }
+                    }),
+                    streaming: None,
+                }
+            }
+            Request::TTest(kind) => {
+                let name = match kind {
+                    TTestKind::OneSample(_) => "one-sample",
+                    TTestKind::Paired => "paired",
+                    TTestKind::Welch => "welch",
+                };
+                let labels: Vec<String> = [
+                    "t",
+                    "df",
+                    "p",
+                    "p-less",
+                    "p-greater",
+                    "difference",
+                    "ci-lower",
+                    "ci-upper",
+                ]
+                .iter()
+                .map(|label| format!("{}-{}", name, label))
+                .collect();
+                let n = labels.len();
+                Stat {
+                    name: format!("{}-t-test", name),
+                    labels,
+                    columns: match kind {
+                        TTestKind::OneSample(_) => 1,
+                        _ => 2,
+                    },
+                    batch: Box::new(move |cols| {
+                        let test = match kind {
+                            TTestKind::OneSample(mu) => checked::one_sample_t_test(&cols[0], mu),
+                            TTestKind::Paired => checked::paired_t_test(&cols[0], &cols[1]),
+                            TTestKind::Welch => checked::welch_t_test(&cols[0], &cols[1]),
+                        };
+                        match test {
+                            Ok(test) => {
+                                let (lower, upper) = test.confidence_interval(settings.confidence);
+                                vec![
+                                    test.statistic,
+                                    test.degrees_of_freedom,
+                                    test.p_value,
+                                    test.p_less,
+                                    test.p_greater,
+                                    test.estimate,
+                                    lower,
+                                    upper,
+                                ]
+                                .into_iter()
+                                .map(Ok)
+                                .collect()
+                            }
+                            Err(e) => vec![Err(e); n],
+                        }
+                    }),
+                    streaming: None,
+                }
+            }
+            Request::Median => Stat {
+                name: "median".to_owned(),
+                labels: vec!["median".to_owned()],
+                columns: 1,
+                batch: Box::new(move |cols| {
+                    vec![checked::median_with(&cols[0], settings.median_policy)]
+                }),
+                streaming: None,
+            },
+            Request::WeightedSampleVariance => Stat {
+                name: "weighted-sample-variance".to_owned(),
+                labels: vec!["weighted-sample-variance".to_owned()],
+                columns: 2,
+                batch: Box::new(move |cols| {
+                    vec![checked::weighted_sample_variance(
+                        &cols[0],
+                        &cols[1],
+                        settings.weight_kind,
+                    )]
+                }),
+                streaming: None,
+            },
+            Request::WeightedQuantiles(quantiles) => {
+                let (labels, ps): (Vec<String>, Vec<f64>) = quantiles.into_iter().unzip();
+                let n = ps.len();
+                Stat {
+                    name: "weighted-quantile".to_owned(),
+                    labels,
+                    columns: 2,
+                    batch: Box::new(move |cols| {
+                        match checked::weighted_quantiles(&cols[0], &cols[1], &ps) {
+                            Ok(qs) => qs.into_iter().map(Ok).collect(),
+                            Err(e) => vec![Err(e); n],
+                        }
+                    }),
+                    streaming: None,
+                }
+            }
+            Request::Mode => Stat {
+                name: "mode".to_owned(),
+                labels: vec!["mode".to_owned()],
+                columns: 1,
+                batch: Box::new(move |cols| {
+                    vec![checked::mode_with(&cols[0], settings.mode_policy)]
+                }),
+                streaming: None,
+            },
+            Request::Iqr => Stat {
+                name: "iqr".to_owned(),
+                labels: vec!["iqr".to_owned()],
+                columns: 1,
+                batch: Box::new(move |cols| vec![checked::iqr(&cols[0], settings.quantile_method)]),
+                streaming: None,
+            },
+            Request::Moment(central, k) => {
+                let name = if central { "central-moment" } else { "moment" };
+                Stat {
+                    name: name.to_owned(),
+                    labels: vec![format!("{}{}", name, k)],
+                    columns: 1,
+                    batch: Box::new(move |cols| {
+                        if central {
+                            vec![checked::central_moment(&cols[0], k)]
+                        } else {
+                            vec![checked::moment(&cols[0], k)]
+                        }
+                    }),
+                    streaming: None,
+                }
+            }
+            Request::Quantiles(quantiles) => {
+                let (labels, ps): (Vec<String>, Vec<f64>) = quantiles.into_iter().unzip();
+                let n = ps.len();
+                Stat {
+                    name: "quantile".to_owned(),
+                    labels,
+                    columns: 1,
+                    batch: Box::new(move |cols| {
+                        match checked::quantiles(&cols[0], &ps, settings.quantile_method) {
+                            Ok(qs) => qs.into_iter().map(Ok).collect(),
+                            Err(e) => vec![Err(e); n],
+                        }
+                    }),
+                    streaming: None,
+                }
+            }
+            Request::Summary => Stat {
+                name: "summary".to_owned(),
+                labels: stats::Summary::LABELS
+                    .iter()
+                    .map(|&l| l.to_owned())
+                    .collect(),
+                columns: 1,
+                batch: Box::new(|cols| match checked::summary(&cols[0]) {
+                    Ok(summary) => summary.values().iter().copied().map(Ok).collect(),
+                    Err(e) => vec![Err(e); stats::Summary::LABELS.len()],
+                }),
+                streaming: None,
+            },
+        }
+    }
+}
+
+/// The input: the named file, or standard input if there
+/// is none. A file that cannot be opened is reported and
+/// ends the program.
+fn input(file: Option<&String>) -> Box<dyn BufRead> {
+    match file {
+        None => Box::new(std::io::stdin().lock()),
+        Some(path) => match std::fs::File::open(path) {
+            Ok(f) => Box::new(std::io::BufReader::new(f)),
+            Err(e) => {
+                eprintln!("error reading input {}: {}", path, e);
+                exit(2);
+            }
+        },
+    }
+}
+
+/// Parse a number of the input. A number that does not parse
+/// is reported and ends the program.
+fn number(v: &str) -> f64 {
+    v.parse().unwrap_or_else(|e| {
+        eprintln!("error parsing number {}: {}", v, e);
+        exit(3);
+    })
+}
 
-    // Read the input.
-    use std::io::BufRead;
-    let nums: Vec<f64> = std::io::stdin()
-        .lock()
+/// Rows of `width` numbers from `input`, or of as many as
+/// the first row has if `width` is [`TABLE`], one row per
+/// line with the numbers separated by whitespace or commas,
+/// with `nan_policy` applied: a row is dropped if it has a
+/// NaN value to skip. Input and parse errors, and NaN values
+/// refused by the policy, are reported and end the program.
+fn rows(
+    input: Box<dyn BufRead>,
+    nan_policy: stats::NanPolicy,
+    mut width: usize,
+) -> impl Iterator<Item = Vec<f64>> {
+    input
         .lines()
-        .map(|s| {
+        .map(move |s| {
             let s = s.unwrap_or_else(|e| {
                 eprintln!("error reading input: {}", e);
-                exit(-1);
+                exit(2);
             });
-            s.parse::<f64>().unwrap_or_else(|e| {
-                eprintln!("error parsing number {}: {}", s, e);
-                exit(-1);
+            let row: Vec<f64> = s
+                .split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|v| !v.is_empty())
+                .map(number)
+                .collect();
+            if width == TABLE {
+                if row.is_empty() {
+                    eprintln!("error parsing line {}: expected a row of numbers", s);
+                    exit(3);
+                }
+                width = row.len();
+            }
+            if row.len() != width {
+                eprintln!("error parsing line {}: expected {} numbers", s, width);
+                exit(3);
+            }
+            row
+        })
+        .filter(move |row| keep(row, nan_policy))
+}
+
+/// Whether to keep `row` under `nan_policy`: not if it has
+/// a NaN value to skip. NaN values refused by the policy are
+/// reported and end the program.
+fn keep(row: &[f64], nan_policy: stats::NanPolicy) -> bool {
+    row.iter().all(|&x| {
+        nan_policy.keep(x).unwrap_or_else(|e| {
+            eprintln!("error: {}", e);
+            exit(exit_code(&e));
+        })
+    })
+}
+
+/// Rows made of one number from each of `files`, read in
+/// step, with `nan_policy` applied to each whole row so that
+/// the numbers stay paired. Files of different lengths, and
+/// errors as for [`rows`], are reported and end the program.
+fn file_rows(files: &[String], nan_policy: stats::NanPolicy) -> impl Iterator<Item = Vec<f64>> {
+    let mut columns: Vec<_> = files
+        .iter()
+        .map(|file| rows(input(Some(file)), stats::NanPolicy::Propagate, 1))
+        .collect();
+    std::iter::from_fn(move || {
+        let row: Vec<Option<f64>> = columns
+            .iter_mut()
+            .map(|column| column.next().map(|row| row[0]))
+            .collect();
+        if row.iter().all(Option::is_none) {
+            return None;
+        }
+        let row = row.into_iter().collect::<Option<Vec<f64>>>();
+        if row.is_none() {
+            eprintln!("error parsing input: the files differ in length");
+            exit(3);
+        }
+        row
+    })
+    .filter(move |row| keep(row, nan_policy))
+}
+
+/// The names and values of groups to compare. With several
+/// `files`, each is a group named by its file name, holding
+/// one number per line. Otherwise the input has lines of a
+/// label and a number, separated by whitespace or a comma,
+/// and the groups are named by the labels in the order they
+/// first appear. Either way `nan_policy` is applied to the
+/// values, and errors end the program as for [`rows`].
+fn groups(files: &[String], nan_policy: stats::NanPolicy) -> (Vec<String>, Vec<Vec<f64>>) {
+    if files.len() > 1 {
+        return files
+            .iter()
+            .map(|file| {
+                let values = rows(input(Some(file)), nan_policy, 1).map(|row| row[0]);
+                (file.clone(), values.collect())
+            })
+            .unzip();
+    }
+    let mut names: Vec<String> = Vec::new();
+    let mut groups: Vec<Vec<f64>> = Vec::new();
+    for s in input(files.first()).lines() {
+        let s = s.unwrap_or_else(|e| {
+            eprintln!("error reading input: {}", e);
+            exit(2);
+        });
+        let fields: Vec<&str> = s
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|v| !v.is_empty())
+            .collect();
+        if fields.len() != 2 {
+            eprintln!("error parsing line {}: expected a label and a number", s);
+            exit(3);
+        }
+        let x = number(fields[1]);
+        let keep = nan_policy.keep(x).unwrap_or_else(|e| {
+            eprintln!("error: {}", e);
+            exit(exit_code(&e));
+        });
+        if !keep {
+            continue;
+        }
+        match names.iter().position(|name| name == fields[0]) {
+            Some(i) => groups[i].push(x),
+            None => {
+                names.push(fields[0].to_owned());
+                groups.push(vec![x]);
+            }
+        }
+    }
+    (names, groups)
+}
+
+/// Print a frequency table of the lines of `input`, taken as
+/// strings: each distinct line with its count and the
+/// proportion of all lines, separated by tabs, most common
+/// first. Then exit.
+fn frequency_table(input: Box<dyn BufRead>) -> ! {
+    let lines: Vec<String> = input
+        .lines()
+        .map(|s| {
+            s.unwrap_or_else(|e| {
+                eprintln!("error reading input: {}", e);
+                exit(2);
             })
         })
         .collect();
+    let total = lines.len() as f64;
+    for (line, count) in stats::frequency_table(lines) {
+        println!("{}\t{}\t{}", line, count, count as f64 / total);
+    }
+    exit(0);
+}
+
+/// Do the computation.
+fn main() {
+    // Process the arguments.
+    let mut settings = Settings {
+        median_policy: stats::MedianPolicy::Lower,
+        mode_policy: stats::ModePolicy::Smallest,
+        nan_policy: stats::NanPolicy::Propagate,
+        quantile_method: stats::QuantileMethod::Linear,
+        weight_kind: stats::WeightKind::Frequency,
+        confidence: 0.95,
+    };
+    let mut requests = Vec::new();
+    let mut frequencies = false;
+    let mut predictions = Vec::new();
+    let mut files = Vec::new();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if let Some(value) = option_value(&arg, "--median-policy", &mut args) {
+            let policies = &[
+                ("lower", stats::MedianPolicy::Lower),
+                ("upper", stats::MedianPolicy::Upper),
+                ("midpoint", stats::MedianPolicy::Midpoint),
+            ];
+            settings.median_policy = choice(policies, &value);
+        } else if let Some(value) = option_value(&arg, "--mode-policy", &mut args) {
+            let policies = &[
+                ("smallest", stats::ModePolicy::Smallest),
+                ("largest", stats::ModePolicy::Largest),
+                ("unique", stats::ModePolicy::Unique),
+            ];
+            settings.mode_policy = choice(policies, &value);
+        } else if let Some(value) = option_value(&arg, "--weights", &mut args) {
+            let kinds = &[
+                ("frequency", stats::WeightKind::Frequency),
+                ("reliability", stats::WeightKind::Reliability),
+            ];
+            settings.weight_kind = choice(kinds, &value);
+        } else if let Some(value) = option_value(&arg, "--nan", &mut args) {
+            let policies = &[
+                ("propagate", stats::NanPolicy::Propagate),
+                ("skip", stats::NanPolicy::Skip),
+                ("error", stats::NanPolicy::Error),
+            ];
+            settings.nan_policy = choice(policies, &value);
+        } else if let Some(value) = option_value(&arg, "--quantile-method", &mut args) {
+            use stats::QuantileMethod::*;
+            let methods = &[
+                ("linear", Linear),
+                ("lower", Lower),
+                ("higher", Higher),
+                ("nearest", Nearest),
+                ("midpoint", Midpoint),
+                ("inverted_cdf", InvertedCdf),
+                ("averaged_inverted_cdf", AveragedInvertedCdf),
+                ("closest_observation", ClosestObservation),
+                ("interpolated_inverted_cdf", InterpolatedInvertedCdf),
+                ("hazen", Hazen),
+                ("weibull", Weibull),
+                ("median_unbiased", MedianUnbiased),
+                ("normal_unbiased", NormalUnbiased),
+            ];
+            settings.quantile_method = choice(methods, &value);
+        } else if let Some(value) = option_value(&arg, "--confidence", &mut args) {
+            settings.confidence = probability(&value, 1.0);
+        } else if let Some(value) = option_value(&arg, "--t-test", &mut args) {
+            requests.push(Request::TTest(TTestKind::OneSample(parameter(&value))));
+        } else if arg == "--paired-t-test" {
+            requests.push(Request::TTest(TTestKind::Paired));
+        } else if arg == "--welch-t-test" {
+            requests.push(Request::TTest(TTestKind::Welch));
+        } else if let Some(value) = option_value(&arg, "--quantile", &mut args) {
+            let p = probability(&value, 1.0);
+            requests.push(Request::Quantiles(vec![(format!("q{}", value), p)]));
+        } else if let Some((flag, stat, value)) = PARAM_ARGDESCS
+            .iter()
+            .find_map(|&(flag, stat)| Some((flag, stat, option_value(&arg, flag, &mut args)?)))
+        {
+            let p = parameter(&value);
+            requests.push(Request::Parameterized(flag, value, stat, p));
+        } else if let Some(value) = option_value(&arg, "--moment", &mut args) {
+            requests.push(Request::Moment(false, order(&value)));
+        } else if let Some(value) = option_value(&arg, "--central-moment", &mut args) {
+            requests.push(Request::Moment(true, order(&value)));
+        } else if let Some(value) = option_value(&arg, "--percentiles", &mut args) {
+            let ps = value
+                .split(',')
+                .map(|v| (format!("p{}", v.trim()), probability(v, 100.0)))
+                .collect();
+            requests.push(Request::Quantiles(ps));
+        } else if let Some(value) = option_value(&arg, "--polynomial-fit", &mut args) {
+            let degree = value.trim().parse().unwrap_or_else(|_| usage());
+            requests.push(Request::Fit(stats::CurveKind::Polynomial, degree));
+        } else if arg == "--exponential-fit" {
+            requests.push(Request::Fit(stats::CurveKind::Exponential, 0));
+        } else if arg == "--power-fit" {
+            requests.push(Request::Fit(stats::CurveKind::PowerLaw, 0));
+        } else if let Some(value) = option_value(&arg, "--predict", &mut args) {
+            predictions.extend(
+                value
+                    .split(',')
+                    .map(|v| (v.trim().to_owned(), parameter(v))),
+            );
+        } else if arg == "--regression" {
+            requests.push(Request::Regression(1));
+        } else if let Some(value) = option_value(&arg, "--multiple-regression", &mut args) {
+            let k = value.trim().parse().unwrap_or_else(|_| usage());
+            requests.push(Request::Regression(k));
+        } else if arg == "--median" {
+            requests.push(Request::Median);
+        } else if let Some(value) = option_value(&arg, "--weighted-quantile", &mut args) {
+            let p = probability(&value, 1.0);
+            requests.push(Request::WeightedQuantiles(vec![(
+                format!("weighted-q{}", value),
+                p,
+            )]));
+        } else if arg == "--weighted-sample-variance" {
+            requests.push(Request::WeightedSampleVariance);
+        } else if arg == "--mode" {
+            requests.push(Request::Mode);
+        } else if arg == "--frequencies" {
+            frequencies = true;
+        } else if arg == "--iqr" {
+            requests.push(Request::Iqr);
+        } else if arg == "--summary" {
+            requests.push(Request::Summary);
+        } else if arg == "--normality" {
+            requests.push(Request::Normality);
+        } else if arg == "--independence" {
+            requests.push(Request::Independence);
+        } else if arg == "--fisher" {
+            requests.push(Request::Fisher);
+        } else if arg == "--anova" {
+            requests.push(Request::Anova);
+        } else if arg == "--tukey" {
+            requests.push(Request::Tukey);
+        } else if arg == "--kruskal-wallis" {
+            requests.push(Request::KruskalWallis);
+        } else if let Some(&(flag, stat)) = PAIR_ARGDESCS.iter().find(|(a, _)| *a == arg) {
+            requests.push(Request::Pair(flag, stat));
+        } else if let Some(&(flag, stat)) = CORRELATION_ARGDESCS.iter().find(|(a, _)| *a == arg) {
+            requests.push(Request::Correlation(flag, stat));
+        } else if let Some(&(flag, test)) = TEST_ARGDESCS.iter().find(|(a, _)| *a == arg) {
+            requests.push(Request::Test(flag, test));
+        } else if !arg.starts_with('-') {
+            files.push(arg);
+        } else {
+            let &(flag, stat, streaming) = ARGDESCS
+                .iter()
+                .find(|(a, _, _)| *a == arg)
+                .unwrap_or_else(|| usage());
+            requests.push(Request::Plain(flag, stat, streaming));
+        }
+    }
+    if frequencies {
+        if !requests.is_empty() || files.len() > 1 {
+            usage();
+        }
+        frequency_table(input(files.first()));
+    }
+    if requests.is_empty() {
+        usage();
+    }
+    if !predictions.is_empty()
+        && !requests
+            .iter()
+            .any(|request| matches!(request, Request::Fit(..)))
+    {
+        usage();
+    }
+    // Groups are read before anything else, since comparisons
+    // of pairs of them are labelled by their names.
+    let groups = if requests.iter().any(Request::grouped) {
+        if !requests.iter().all(Request::grouped) {
+            eprintln!("stats: statistics of groups cannot be mixed with others");
+            usage();
+        }
+        Some(groups(&files, settings.nan_policy))
+    } else {
+        None
+    };
+    let names = groups.as_ref().map_or(&[][..], |(names, _)| names);
+    let independent = requests.iter().all(Request::independent);
+    let stats: Vec<Stat> = requests
+        .into_iter()
+        .map(|r| r.stat(settings, &predictions, names))
+        .collect();
+    let width = stats[0].columns;
+    if stats.iter().any(|stat| stat.columns != width) {
+        eprintln!("stats: statistics of different numbers of columns cannot be mixed");
+        usage();
+    }
+    if files.len() > 1 && width == TABLE {
+        eprintln!("stats: a table must be read from a single file");
+        usage();
+    }
+    if files.len() > 1 && width != GROUPS && files.len() != width {
+        eprintln!(
+            "stats: {} input files given for {} columns",
+            files.len(),
+            width
+        );
+        usage();
+    }
+
+    // Run the stats over the input, which is read only once:
+    // it is streamed through accumulators when every stat
+    // allows it, and collected otherwise. With one file per
+    // column, the files are read in step, as the columns of
+    // one file would be; only independent samples are read
+    // whole, one file at a time, so that they may differ in
+    // length and the policy for NaN applies to each alone.
+    let results: Vec<Vec<Result<f64, StatsError>>> = if let Some(new_accs) = stats
+        .iter()
+        .map(|stat| stat.streaming)
+        .collect::<Option<Vec<NewAccFn>>>()
+    {
+        let mut accs: Vec<Box<dyn Accumulator>> =
+            new_accs.iter().map(|new_acc| new_acc()).collect();
+        for row in rows(input(files.first()), settings.nan_policy, width) {
+            for acc in &mut accs {
+                acc.push(row[0]);
+            }
+        }
+        accs.iter().map(|acc| vec![acc.try_result()]).collect()
+    } else {
+        let cols = if let Some((_, groups)) = groups {
+            groups
+        } else {
+            let mut cols = vec![Vec::new(); width];
+            if files.len() > 1 && independent {
+                for (col, file) in cols.iter_mut().zip(&files) {
+                    col.extend(rows(input(Some(file)), settings.nan_policy, 1).map(|row| row[0]));
+                }
+            } else if files.len() > 1 {
+                for row in file_rows(&files, settings.nan_policy) {
+                    for (col, x) in cols.iter_mut().zip(row) {
+                        col.push(x);
+                    }
+                }
+            } else {
+                for row in rows(input(files.first()), settings.nan_policy, width) {
+                    // A table has as many columns as its rows.
+                    if cols.len() < row.len() {
+                        cols.resize(row.len(), Vec::new());
+                    }
+                    for (col, x) in cols.iter_mut().zip(row) {
+                        col.push(x);
+                    }
+                }
+            }
+            cols
+        };
+        stats
+            .iter()
+            .map(|stat| {
+                (stat.batch)(&cols)
+                    .into_iter()
+                    .map(|result| match result {
+                        // Only NaN values let through by the policy
+                        // get here, and they propagate into the
+                        // result.
+                        Err(StatsError::ContainsNan) => Ok(f64::NAN),
+                        result => result,
+                    })
+                    .collect()
+            })
+            .collect()
+    };
 
-    // Run the stat, show the result if any.
-    if let Some(result) = stat(&nums) {
-        println!("{}", result);
+    // Show the results. A single result is shown bare;
+    // several are labelled. Statistics that could not be
+    // computed are reported, and the first of them sets the
+    // exit status.
+    let count: usize = results.iter().map(Vec::len).sum();
+    let mut status = 0;
+    for (stat, results) in stats.iter().zip(&results) {
+        let mut reported = None;
+        for (label, result) in stat.labels.iter().zip(results) {
+            match result {
+                Ok(result) if count == 1 => println!("{}", result),
+                Ok(result) => println!("{}: {}", label, result),
+                Err(e) => {
+                    if count > 1 {
+                        println!("{}: undefined", label);
+                    }
+                    if reported != Some(e) {
+                        eprintln!("stats: {}: {}", stat.name, e);
+                        reported = Some(e);
+                    }
+                    if status == 0 {
+                        status = exit_code(e);
+                    }
+                }
+            }
+        }
     }
+    exit(status);
 }
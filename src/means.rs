@@ -0,0 +1,146 @@
+// Copyright © 2019 Bader Alshaya
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Means other than the arithmetic mean: the geometric and
+//! harmonic means, and the power means that generalize them.
+//! These are only defined for values of zero or more; a
+//! negative input value makes them undefined.
+
+use crate::accumulator::accumulate;
+use crate::{Accumulator, RunningGeometricMean, RunningHarmonicMean, RunningMean, StatsError};
+
+/// Geometric mean of input values: the `n`-th root of their
+/// product. It is computed as the exponential of the mean
+/// logarithm, so the product never overflows. The geometric
+/// mean is 0.0 if any value is zero, and undefined for an
+/// empty list or if any value is negative.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert!((geometric_mean(&[2.0, 8.0]).unwrap() - 4.0).abs() < 1e-12);
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(0.0), geometric_mean(&[0.0, 5.0]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, geometric_mean(&[-1.0, 1.0]));
+/// assert_eq!(None, geometric_mean(&[]));
+/// ```
+pub fn geometric_mean(nums: &[f64]) -> Option<f64> {
+    accumulate(RunningGeometricMean::default(), nums)
+}
+
+/// Harmonic mean of input values: the reciprocal of the
+/// mean of their reciprocals. The harmonic mean is 0.0 if
+/// any value is zero, and undefined for an empty list or if
+/// any value is negative.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(2.0), harmonic_mean(&[1.0, 4.0, 4.0]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(0.0), harmonic_mean(&[0.0, 5.0]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, harmonic_mean(&[-1.0, 1.0]));
+/// ```
+pub fn harmonic_mean(nums: &[f64]) -> Option<f64> {
+    accumulate(RunningHarmonicMean::default(), nums)
+}
+
+/// Power mean of input values with exponent `p`: the `p`-th
+/// root of the mean of their `p`-th powers. This is the
+/// arithmetic mean for `p` = 1, the harmonic mean for `p` =
+/// −1 and, in the limit, the geometric mean for `p` = 0, the
+/// maximum for `p` = ∞ and the minimum for `p` = −∞. The
+/// values are scaled by the maximum (or, for negative `p`,
+/// the minimum) before taking powers, so that large
+/// exponents do not overflow. The power mean is undefined
+/// for an empty list, if any value is negative, or if `p` is
+/// NaN. Apart from those cases, a zero value makes the power
+/// mean 0.0 for `p` ≤ 0.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// // Root mean square.
+/// assert_eq!(Some(5.0), power_mean(&[1.0, 7.0], 2.0));
+/// ```
+/// ```
+/// # use stats::*;
+/// let nums = [1.0, 2.0, 4.0];
+/// assert_eq!(mean(&nums), power_mean(&nums, 1.0));
+/// assert_eq!(geometric_mean(&nums), power_mean(&nums, 0.0));
+/// assert_eq!(harmonic_mean(&nums), power_mean(&nums, -1.0));
+/// assert_eq!(Some(4.0), power_mean(&nums, f64::INFINITY));
+/// assert_eq!(Some(1.0), power_mean(&nums, f64::NEG_INFINITY));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert!((power_mean(&[1e300, 1e300], 3.0).unwrap() / 1e300 - 1.0).abs() < 1e-12);
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, power_mean(&[], 2.0));
+/// assert_eq!(None, power_mean(&[1.0], f64::NAN));
+/// ```
+pub fn power_mean(nums: &[f64], p: f64) -> Option<f64> {
+    try_power_mean(nums, p).ok()
+}
+
+/// Power mean, or why it is undefined. Like an
+/// [`Accumulator`], this never reports
+/// [`StatsError::ContainsNan`]: NaN values propagate into
+/// the result.
+pub(crate) fn try_power_mean(nums: &[f64], p: f64) -> Result<f64, StatsError> {
+    if p.is_nan() {
+        return Err(StatsError::InvalidParameter("exponent must not be NaN"));
+    }
+    if p == 0.0 {
+        return try_accumulate(RunningGeometricMean::default(), nums);
+    }
+    if p == -1.0 {
+        return try_accumulate(RunningHarmonicMean::default(), nums);
+    }
+    StatsError::check_len(nums.len(), 1)?;
+    if nums.iter().any(|x| x.is_nan()) {
+        return Ok(f64::NAN);
+    }
+    if nums.iter().any(|&x| x < 0.0) {
+        return Err(StatsError::NegativeValue);
+    }
+    if p == 1.0 {
+        return try_accumulate(RunningMean::default(), nums);
+    }
+    let (min, max) = nums.iter().fold((f64::INFINITY, 0.0f64), |(min, max), &x| {
+        (min.min(x), max.max(x))
+    });
+    let scale = if p > 0.0 { max } else { min };
+    if p.is_infinite() || scale == 0.0 || scale.is_infinite() {
+        return Ok(scale);
+    }
+    let mut acc = RunningMean::default();
+    for &x in nums {
+        acc.push((x / scale).powf(p));
+    }
+    Ok(scale * acc.try_result()?.powf(p.recip()))
+}
+
+/// Push all of `nums` into `acc` and return its result, or
+/// why it is undefined.
+fn try_accumulate<A: Accumulator>(mut acc: A, nums: &[f64]) -> Result<f64, StatsError> {
+    acc.push_all(nums);
+    acc.try_result()
+}
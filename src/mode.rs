@@ -0,0 +1,141 @@
+// Copyright © 2019 Bader Alshaya
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! The most common values, and tables of how often each
+//! value occurs. Frequency tables work on any ordered values,
+//! such as strings, not just numbers.
+
+use std::collections::BTreeMap;
+
+use crate::select::compare;
+
+/// How to pick the mode of multimodal values, which have
+/// several most common values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModePolicy {
+    /// The smallest of the most common values.
+    Smallest,
+    /// The largest of the most common values.
+    Largest,
+    /// No value: the mode of multimodal values is undefined.
+    Unique,
+}
+
+/// The most common of the input values, all of them, in
+/// increasing order. Values are counted as equal when they
+/// compare equal, so 0.0 and -0.0 are counted together. If
+/// all values are distinct, every value is a mode. There
+/// are no modes of an empty list, and the only mode of
+/// values including a NaN is NaN.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(vec![2.0], modes(&[1.0, 2.0, 2.0, 3.0]));
+/// assert_eq!(vec![1.0, 3.0], modes(&[3.0, 1.0, 2.0, 3.0, 1.0]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert!(modes(&[]).is_empty());
+/// ```
+pub fn modes(nums: &[f64]) -> Vec<f64> {
+    if nums.iter().any(|x| x.is_nan()) {
+        return vec![f64::NAN];
+    }
+    let mut sorted = nums.to_owned();
+    sorted.sort_unstable_by(compare);
+    let mut modes = Vec::new();
+    let mut best = 0;
+    for run in sorted.chunk_by(|a, b| a == b) {
+        if run.len() > best {
+            best = run.len();
+            modes.clear();
+        }
+        if run.len() == best {
+            modes.push(run[0]);
+        }
+    }
+    modes
+}
+
+/// Mode of input values: the most common value, taking the
+/// smallest to break ties. The mode of an empty list is
+/// undefined. This is [`mode_with`] using
+/// [`ModePolicy::Smallest`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(1.0), mode(&[3.0, 1.0, 2.0, 3.0, 1.0]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, mode(&[]));
+/// ```
+pub fn mode(nums: &[f64]) -> Option<f64> {
+    mode_with(nums, ModePolicy::Smallest)
+}
+
+/// Mode of input values, using `policy` to choose between
+/// several most common values. The mode of an empty list is
+/// undefined.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// let nums = [3.0, 1.0, 2.0, 3.0, 1.0];
+/// assert_eq!(Some(1.0), mode_with(&nums, ModePolicy::Smallest));
+/// assert_eq!(Some(3.0), mode_with(&nums, ModePolicy::Largest));
+/// assert_eq!(None, mode_with(&nums, ModePolicy::Unique));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(2.0), mode_with(&[2.0, 1.0, 2.0], ModePolicy::Unique));
+/// ```
+pub fn mode_with(nums: &[f64], policy: ModePolicy) -> Option<f64> {
+    let modes = modes(nums);
+    match policy {
+        ModePolicy::Smallest => modes.first().copied(),
+        ModePolicy::Largest => modes.last().copied(),
+        ModePolicy::Unique if modes.len() == 1 => Some(modes[0]),
+        ModePolicy::Unique => None,
+    }
+}
+
+/// Frequency table of the input values: each distinct value
+/// with the number of times it occurs, most common first,
+/// and values that occur equally often in increasing order.
+/// The values may be of any ordered type, such as strings.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// let codes = ["200", "404", "200", "500", "404", "200"];
+/// assert_eq!(
+///     vec![("200", 3), ("404", 2), ("500", 1)],
+///     frequency_table(codes)
+/// );
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(vec![(1, 2), (2, 2)], frequency_table(vec![2, 1, 1, 2]));
+/// ```
+pub fn frequency_table<I>(values: I) -> Vec<(I::Item, usize)>
+where
+    I: IntoIterator,
+    I::Item: Ord,
+{
+    let mut counts = BTreeMap::new();
+    for value in values {
+        *counts.entry(value).or_insert(0) += 1;
+    }
+    let mut table: Vec<_> = counts.into_iter().collect();
+    table.sort_by_key(|&(_, count)| std::cmp::Reverse(count));
+    table
+}
@@ -0,0 +1,176 @@
+// Copyright © 2019 Bader Alshaya
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Higher moments: raw and central moments of any order,
+//! and the skewness and kurtosis that describe the shape of
+//! a distribution.
+
+use crate::accumulator::{accumulate, CompensatedSum};
+use crate::{RunningKurtosis, RunningSkewness};
+
+/// Mean of `f` applied to each input value, with
+/// compensated summation. Undefined for an empty list.
+fn mean_of(nums: &[f64], f: impl Fn(f64) -> f64) -> Option<f64> {
+    if nums.is_empty() {
+        return None;
+    }
+    let mut sum = CompensatedSum::default();
+    for &x in nums {
+        sum.add(f(x));
+    }
+    Some(sum.value() / nums.len() as f64)
+}
+
+/// `x` to the power `k`, which may be too large for
+/// [`f64::powi`].
+fn power(x: f64, k: u32) -> f64 {
+    if k <= i32::MAX as u32 {
+        x.powi(k as i32)
+    } else {
+        x.powf(f64::from(k))
+    }
+}
+
+/// The `k`-th raw moment of the input values: the mean of
+/// their `k`-th powers. The moments of an empty list are
+/// undefined.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(30.875), moment(&[2.0, 8.0, 0.0, 4.0, 1.0, 9.0, 9.0, 0.0], 2));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(1.0), moment(&[3.0, 4.0], 0));
+/// assert_eq!(Some(f64::INFINITY), moment(&[2.0], u32::MAX));
+/// assert_eq!(Some(-1.0), moment(&[-1.0], u32::MAX));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, moment(&[], 1));
+/// ```
+pub fn moment(nums: &[f64], k: u32) -> Option<f64> {
+    mean_of(nums, |x| power(x, k))
+}
+
+/// The `k`-th central moment of the input values: the mean
+/// of the `k`-th powers of their deviations from the mean.
+/// The second central moment is the population variance.
+/// Computed in two passes, the first finding the mean. The
+/// moments of an empty list are undefined.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// let nums = [2.0, 8.0, 0.0, 4.0, 1.0, 9.0, 9.0, 0.0];
+/// assert_eq!(Some(13.859375), central_moment(&nums, 2));
+/// assert_eq!(Some(13.67578125), central_moment(&nums, 3));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(0.0), central_moment(&[1.0, 2.0, 3.0], 1));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, central_moment(&[], 2));
+/// ```
+pub fn central_moment(nums: &[f64], k: u32) -> Option<f64> {
+    let mean = crate::mean(nums)?;
+    mean_of(nums, |x| power(x - mean, k))
+}
+
+/// Population skewness of the input values: the third
+/// central moment divided by the 3/2 power of the second.
+/// The skewness is undefined for an empty list, or if all
+/// values are equal.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// let nums = [2.0, 8.0, 0.0, 4.0, 1.0, 9.0, 9.0, 0.0];
+/// assert!((skewness(&nums).unwrap() - 0.2650554122698573).abs() < 1e-12);
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(0.0), skewness(&[1.0, 2.0, 3.0]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, skewness(&[5.0, 5.0]));
+/// ```
+pub fn skewness(nums: &[f64]) -> Option<f64> {
+    accumulate(RunningSkewness::default(), nums)
+}
+
+/// Sample skewness of the input values: the population
+/// skewness corrected for bias (the adjusted
+/// Fisher–Pearson coefficient, as computed by spreadsheets).
+/// The sample skewness is undefined for fewer than three
+/// values, or if all values are equal.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// let nums = [2.0, 8.0, 0.0, 4.0, 1.0, 9.0, 9.0, 0.0];
+/// assert!((sample_skewness(&nums).unwrap() - 0.33058218040797466).abs() < 1e-12);
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, sample_skewness(&[1.0, 2.0]));
+/// ```
+pub fn sample_skewness(nums: &[f64]) -> Option<f64> {
+    accumulate(RunningSkewness::sample(), nums)
+}
+
+/// Population excess kurtosis of the input values: the
+/// fourth central moment divided by the square of the
+/// second, less 3 so that the normal distribution has
+/// kurtosis 0. The kurtosis is undefined for an empty list,
+/// or if all values are equal.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// let nums = [2.0, 8.0, 0.0, 4.0, 1.0, 9.0, 9.0, 0.0];
+/// assert!((kurtosis(&nums).unwrap() - -1.6660010752838508).abs() < 1e-12);
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(-2.0), kurtosis(&[-1.0, 1.0]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, kurtosis(&[]));
+/// ```
+pub fn kurtosis(nums: &[f64]) -> Option<f64> {
+    accumulate(RunningKurtosis::default(), nums)
+}
+
+/// Sample excess kurtosis of the input values: the
+/// population excess kurtosis corrected for bias, as
+/// computed by spreadsheets. The sample kurtosis is
+/// undefined for fewer than four values, or if all values
+/// are equal.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// let nums = [2.0, 8.0, 0.0, 4.0, 1.0, 9.0, 9.0, 0.0];
+/// assert!((sample_kurtosis(&nums).unwrap() - -2.098602258096087).abs() < 1e-12);
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, sample_kurtosis(&[1.0, 2.0, 3.0]));
+/// ```
+pub fn sample_kurtosis(nums: &[f64]) -> Option<f64> {
+    accumulate(RunningKurtosis::sample(), nums)
+}
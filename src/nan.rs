@@ -0,0 +1,112 @@
+// Copyright © 2019 Bader Alshaya
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Treatment of NaN values in the input.
+//!
+//! Left to themselves, all statistics in this crate
+//! propagate NaN: if any input value is NaN, the result is
+//! NaN (or undefined, if the statistic is undefined for the
+//! input anyway). A [`NanPolicy`] lets the caller skip NaN
+//! values or reject them instead. Infinities are ordinary
+//! values: they sort below or above every finite value, and
+//! arithmetic on them follows IEEE 754, so for example the
+//! mean of `inf` and `-inf` is NaN.
+
+use std::borrow::Cow;
+
+use crate::StatsError;
+
+/// How NaN values in the input are treated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NanPolicy {
+    /// Keep NaN values, so that they propagate into the
+    /// result.
+    #[default]
+    Propagate,
+    /// Drop NaN values and compute the statistic on the
+    /// rest.
+    Skip,
+    /// Refuse input containing NaN values, with
+    /// [`StatsError::ContainsNan`].
+    Error,
+}
+
+impl NanPolicy {
+    /// Whether the input value `x` should be kept. This
+    /// allows the policy to be applied to values one at a
+    /// time, for example before pushing them into an
+    /// [`Accumulator`](crate::Accumulator).
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use stats::*;
+    /// assert_eq!(Ok(true), NanPolicy::Skip.keep(1.0));
+    /// assert_eq!(Ok(false), NanPolicy::Skip.keep(f64::NAN));
+    /// assert_eq!(Ok(true), NanPolicy::Propagate.keep(f64::NAN));
+    /// assert_eq!(Err(StatsError::ContainsNan), NanPolicy::Error.keep(f64::NAN));
+    /// ```
+    pub fn keep(self, x: f64) -> Result<bool, StatsError> {
+        if !x.is_nan() {
+            return Ok(true);
+        }
+        match self {
+            NanPolicy::Propagate => Ok(true),
+            NanPolicy::Skip => Ok(false),
+            NanPolicy::Error => Err(StatsError::ContainsNan),
+        }
+    }
+
+    /// The input values with the policy applied. The input is
+    /// only copied if NaN values have to be dropped.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use stats::*;
+    /// let nums = [1.0, f64::NAN, 3.0];
+    /// assert_eq!(&[1.0, 3.0][..], &*NanPolicy::Skip.apply(&nums).unwrap());
+    /// assert_eq!(Err(StatsError::ContainsNan), NanPolicy::Error.apply(&nums));
+    /// ```
+    pub fn apply(self, nums: &[f64]) -> Result<Cow<'_, [f64]>, StatsError> {
+        if !nums.iter().any(|x| x.is_nan()) {
+            return Ok(Cow::Borrowed(nums));
+        }
+        match self {
+            NanPolicy::Propagate => Ok(Cow::Borrowed(nums)),
+            NanPolicy::Skip => Ok(Cow::Owned(
+                nums.iter().copied().filter(|x| !x.is_nan()).collect(),
+            )),
+            NanPolicy::Error => Err(StatsError::ContainsNan),
+        }
+    }
+}
+
+/// Compute `stat` on the input values with `policy` applied
+/// to any NaN values among them.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// let nums = [3.0, f64::NAN, 1.0, 2.0];
+/// assert!(with_nan_policy(median, &nums, NanPolicy::Propagate).unwrap().unwrap().is_nan());
+/// assert_eq!(Ok(Some(2.0)), with_nan_policy(median, &nums, NanPolicy::Skip));
+/// assert_eq!(Err(StatsError::ContainsNan), with_nan_policy(median, &nums, NanPolicy::Error));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Ok(None), with_nan_policy(stddev, &[f64::NAN], NanPolicy::Skip));
+/// ```
+pub fn with_nan_policy<F>(
+    stat: F,
+    nums: &[f64],
+    policy: NanPolicy,
+) -> Result<Option<f64>, StatsError>
+where
+    F: Fn(&[f64]) -> Option<f64>,
+{
+    Ok(stat(&policy.apply(nums)?))
+}
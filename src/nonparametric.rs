@@ -0,0 +1,512 @@
+// Copyright © 2019 Bader Alshaya
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Nonparametric tests, which compare samples through their
+//! ranks or empirical distributions rather than assuming a
+//! normal distribution. Small samples get exact p-values;
+//! larger ones, or ones with ties, get large-sample
+//! approximations. A NaN value makes the whole result NaN.
+
+use crate::distributions::Distribution;
+use crate::rank::tie_sizes;
+use crate::select::compare;
+use crate::special::{kolmogorov_sf, normal_two_sided};
+use crate::{ranks, StatsError};
+
+/// The result of a hypothesis test: its statistic, with the
+/// two-sided p-value of the null hypothesis.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TestResult {
+    /// The test statistic.
+    pub statistic: f64,
+    /// Probability of a statistic at least this extreme if
+    /// the null hypothesis were true.
+    pub p_value: f64,
+}
+
+impl TestResult {
+    /// Result for input containing NaN.
+    pub(crate) const NAN: TestResult = TestResult {
+        statistic: f64::NAN,
+        p_value: f64::NAN,
+    };
+}
+
+/// Sample sizes below which the rank tests are exact.
+const EXACT_RANK_SIZE: usize = 50;
+
+/// Sample size below which the one-sample
+/// Kolmogorov-Smirnov test is exact.
+const EXACT_KS_SIZE: usize = 100;
+
+/// Product of sample sizes below which the two-sample
+/// Kolmogorov-Smirnov test is exact.
+const EXACT_KS_PRODUCT: usize = 10_000;
+
+/// Sorted copy of `nums`, which must not contain NaN.
+fn sorted(nums: &[f64]) -> Vec<f64> {
+    let mut sorted = nums.to_owned();
+    sorted.sort_unstable_by(compare);
+    sorted
+}
+
+/// Sum of t³ - t over the sizes t of the groups of tied
+/// values in `sorted`, which is zero if there are no ties.
+fn tie_correction(sorted: &[f64]) -> f64 {
+    tie_sizes(sorted)
+        .map(|t| {
+            let t = t as f64;
+            t * t * t - t
+        })
+        .sum()
+}
+
+/// Two-sided p-value of `statistic` under a discrete null
+/// distribution on 0, 1, 2, … given by the relative
+/// frequencies `counts`: twice the smaller tail, at most 1.
+fn exact_p_value(counts: &[f64], statistic: f64) -> f64 {
+    let total: f64 = counts.iter().sum();
+    let k = statistic.round() as usize;
+    let lower: f64 = counts[..=k].iter().sum();
+    let upper: f64 = counts[k..].iter().sum();
+    (2.0 * lower.min(upper) / total).min(1.0)
+}
+
+/// Two-sided p-value of a statistic of mean `mean` and
+/// standard deviation `sd` under the null hypothesis, by the
+/// normal approximation with a continuity correction of one
+/// half toward the mean.
+fn normal_p_value(statistic: f64, mean: f64, sd: f64) -> f64 {
+    let deviation = statistic - mean;
+    let correction = if deviation == 0.0 {
+        0.0
+    } else {
+        0.5f64.copysign(deviation)
+    };
+    normal_two_sided((deviation - correction) / sd)
+}
+
+/// Frequencies of the Mann-Whitney U statistic of samples of
+/// sizes `m` and `n` without ties, for U from 0 to `m n`.
+/// The arrangements of the combined sample in order are
+/// counted by whether the largest value is from the first
+/// sample, which adds `n` to U, or from the second.
+fn mann_whitney_counts(m: usize, n: usize) -> Vec<f64> {
+    // counts[j] holds the frequencies for i values of the
+    // first sample and j of the second.
+    let mut counts = vec![vec![1.0]; n + 1];
+    for i in 1..=m {
+        let mut next = vec![vec![1.0]];
+        for j in 1..=n {
+            let mut c = vec![0.0; i * j + 1];
+            for (u, &k) in counts[j].iter().enumerate() {
+                c[u + j] += k;
+            }
+            for (u, &k) in next[j - 1].iter().enumerate() {
+                c[u] += k;
+            }
+            next.push(c);
+        }
+        counts = next;
+    }
+    counts.pop().unwrap()
+}
+
+/// Mann-Whitney U test (also called the Wilcoxon rank-sum
+/// test) of the hypothesis that two independent samples come
+/// from the same distribution, against the alternative that
+/// values from one tend to be larger. The statistic is U of
+/// `xs`: the number of pairs of a value from `xs` and one
+/// from `ys` where the `xs` value is larger, with ties
+/// counting one half.
+///
+/// The p-value is exact if both samples have fewer than 50
+/// values and there are no ties. Otherwise it is from the
+/// normal approximation, with the variance corrected for
+/// ties and a continuity correction. The test is undefined
+/// if either sample is empty, or if all the values are
+/// equal.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// let xs = [0.80, 0.83, 1.89, 1.04, 1.45, 1.38, 1.91, 1.64, 0.73, 1.46];
+/// let ys = [1.15, 0.88, 0.90, 0.74, 1.21];
+/// let test = mann_whitney_u(&xs, &ys).unwrap();
+/// assert_eq!(35.0, test.statistic);
+/// assert!((test.p_value - 0.2544123).abs() < 1e-7);
+/// ```
+/// ```
+/// # use stats::*;
+/// // With ties, by the normal approximation.
+/// let test = mann_whitney_u(&[1.0, 2.0, 2.0, 3.0], &[3.0, 4.0, 4.0, 5.0]).unwrap();
+/// assert_eq!(0.5, test.statistic);
+/// assert!((test.p_value - 0.03960870).abs() < 1e-8);
+/// ```
+pub fn mann_whitney_u(xs: &[f64], ys: &[f64]) -> Option<TestResult> {
+    try_mann_whitney_u(xs, ys).ok()
+}
+
+/// Mann-Whitney U test, or why it is undefined.
+pub(crate) fn try_mann_whitney_u(xs: &[f64], ys: &[f64]) -> Result<TestResult, StatsError> {
+    StatsError::check_len(xs.len(), 1)?;
+    StatsError::check_len(ys.len(), 1)?;
+    let combined: Vec<f64> = xs.iter().chain(ys).copied().collect();
+    if combined.iter().any(|x| x.is_nan()) {
+        return Ok(TestResult::NAN);
+    }
+    let (m, n) = (xs.len(), ys.len());
+    let (mf, nf) = (m as f64, n as f64);
+    let rank_sum: f64 = ranks(&combined)[..m].iter().sum();
+    let u = rank_sum - mf * (mf + 1.0) / 2.0;
+    let ties = tie_correction(&sorted(&combined));
+    let p_value = if m < EXACT_RANK_SIZE && n < EXACT_RANK_SIZE && ties == 0.0 {
+        exact_p_value(&mann_whitney_counts(m, n), u)
+    } else {
+        let total = mf + nf;
+        let variance = mf * nf / 12.0 * (total + 1.0 - ties / (total * (total - 1.0)));
+        if variance <= 0.0 {
+            return Err(StatsError::ZeroVariance);
+        }
+        normal_p_value(u, mf * nf / 2.0, variance.sqrt())
+    };
+    Ok(TestResult {
+        statistic: u,
+        p_value,
+    })
+}
+
+/// Frequencies of the Wilcoxon signed-rank statistic of `n`
+/// differences without ties or zeros, for values from 0 to
+/// `n (n + 1) / 2`: each rank is either counted or not.
+fn signed_rank_counts(n: usize) -> Vec<f64> {
+    let mut counts = vec![1.0];
+    for k in 1..=n {
+        let mut next = counts.clone();
+        next.resize(counts.len() + k, 0.0);
+        for (v, &c) in counts.iter().enumerate() {
+            next[v + k] += c;
+        }
+        counts = next;
+    }
+    counts
+}
+
+/// Wilcoxon signed-rank test of the hypothesis that the
+/// differences `xs[i] - ys[i]` of paired values are
+/// symmetric about zero, as for measurements of the same
+/// subjects before and after a treatment. The differences
+/// are ranked by absolute value, dropping zeros, and the
+/// statistic is the sum of the ranks of the positive ones.
+///
+/// The p-value is exact if there are fewer than 50 nonzero
+/// differences, with no zeros and no ties among their
+/// absolute values. Otherwise it is from the normal
+/// approximation, with the variance corrected for ties and
+/// a continuity correction. The test is undefined for empty
+/// input, or if all the differences are zero.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// // Depression scores before and after therapy, from
+/// // Hollander and Wolfe.
+/// let before = [1.83, 0.50, 1.62, 2.48, 1.68, 1.88, 1.55, 3.06, 1.30];
+/// let after = [0.878, 0.647, 0.598, 2.05, 1.06, 1.29, 1.06, 3.14, 1.29];
+/// let test = wilcoxon_signed_rank(&before, &after).unwrap();
+/// assert_eq!(40.0, test.statistic);
+/// assert!((test.p_value - 0.0390625).abs() < 1e-15);
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, wilcoxon_signed_rank(&[1.0, 2.0], &[1.0, 2.0]));
+/// ```
+pub fn wilcoxon_signed_rank(xs: &[f64], ys: &[f64]) -> Option<TestResult> {
+    try_wilcoxon_signed_rank(xs, ys).ok()
+}
+
+/// Wilcoxon signed-rank test, or why it is undefined.
+pub(crate) fn try_wilcoxon_signed_rank(xs: &[f64], ys: &[f64]) -> Result<TestResult, StatsError> {
+    if xs.len() != ys.len() {
+        return Err(StatsError::LengthMismatch);
+    }
+    StatsError::check_len(xs.len(), 1)?;
+    let differences: Vec<f64> = xs.iter().zip(ys).map(|(x, y)| x - y).collect();
+    if differences.iter().any(|d| d.is_nan()) {
+        return Ok(TestResult::NAN);
+    }
+    let nonzero: Vec<f64> = differences.iter().copied().filter(|&d| d != 0.0).collect();
+    if nonzero.is_empty() {
+        return Err(StatsError::ZeroVariance);
+    }
+    let magnitudes: Vec<f64> = nonzero.iter().map(|d| d.abs()).collect();
+    let v: f64 = ranks(&magnitudes)
+        .iter()
+        .zip(&nonzero)
+        .filter(|&(_, &d)| d > 0.0)
+        .map(|(r, _)| r)
+        .sum();
+    let n = nonzero.len();
+    let nf = n as f64;
+    let ties = tie_correction(&sorted(&magnitudes));
+    let p_value = if n < EXACT_RANK_SIZE && ties == 0.0 && n == differences.len() {
+        exact_p_value(&signed_rank_counts(n), v)
+    } else {
+        let variance = nf * (nf + 1.0) * (2.0 * nf + 1.0) / 24.0 - ties / 48.0;
+        normal_p_value(v, nf * (nf + 1.0) / 4.0, variance.sqrt())
+    };
+    Ok(TestResult {
+        statistic: v,
+        p_value,
+    })
+}
+
+/// Probability that the one-sample Kolmogorov-Smirnov
+/// statistic of `n` values is less than `d`, by the method
+/// of Marsaglia, Tsang and Wang, "Evaluating Kolmogorov's
+/// Distribution" (2003): an entry of the `n`th power of a
+/// small matrix, with its exponent tracked separately so
+/// that it neither overflows nor underflows.
+fn kolmogorov_cdf(n: usize, d: f64) -> f64 {
+    let nf = n as f64;
+    let k = (nf * d) as usize + 1;
+    let m = 2 * k - 1;
+    let h = k as f64 - nf * d;
+    let mut matrix = vec![vec![0.0; m]; m];
+    for (i, row) in matrix.iter_mut().enumerate() {
+        for (j, entry) in row.iter_mut().enumerate() {
+            if i + 1 >= j {
+                *entry = 1.0;
+            }
+        }
+    }
+    for (i, row) in matrix.iter_mut().enumerate() {
+        row[0] -= h.powi(i as i32 + 1);
+    }
+    for (i, entry) in matrix[m - 1].iter_mut().enumerate() {
+        *entry -= h.powi((m - i) as i32);
+    }
+    if 2.0 * h - 1.0 > 0.0 {
+        matrix[m - 1][0] += (2.0 * h - 1.0).powi(m as i32);
+    }
+    for (i, row) in matrix.iter_mut().enumerate() {
+        for (j, entry) in row.iter_mut().enumerate() {
+            // Divide by (i - j + 1)!.
+            for g in 2..(i + 2).saturating_sub(j) {
+                *entry /= g as f64;
+            }
+        }
+    }
+    let (power, mut exponent) = matrix_power(&matrix, n, k - 1);
+    let mut s = power[k - 1][k - 1];
+    // Multiply by n! / nⁿ.
+    for i in 1..=n {
+        s *= i as f64 / nf;
+        if s < 1e-140 {
+            s *= 1e140;
+            exponent -= 140;
+        }
+    }
+    s * 10f64.powi(exponent)
+}
+
+/// Product of square matrices.
+fn matrix_product(a: &[Vec<f64>], b: &[Vec<f64>]) -> Vec<Vec<f64>> {
+    a.iter()
+        .map(|row| {
+            (0..b.len())
+                .map(|j| row.iter().zip(b).map(|(x, b_row)| x * b_row[j]).sum())
+                .collect()
+        })
+        .collect()
+}
+
+/// The `n`th power of `matrix`, by repeated squaring, as a
+/// matrix and a power of ten that scales it. The scale is
+/// adjusted whenever the diagonal entry `watch` grows large.
+fn matrix_power(matrix: &[Vec<f64>], n: usize, watch: usize) -> (Vec<Vec<f64>>, i32) {
+    if n == 1 {
+        return (matrix.to_vec(), 0);
+    }
+    let (half, half_exponent) = matrix_power(matrix, n / 2, watch);
+    let mut power = matrix_product(&half, &half);
+    let mut exponent = 2 * half_exponent;
+    if n % 2 == 1 {
+        power = matrix_product(matrix, &power);
+    }
+    if power[watch][watch] > 1e140 {
+        for row in &mut power {
+            for x in row {
+                *x *= 1e-140;
+            }
+        }
+        exponent += 140;
+    }
+    (power, exponent)
+}
+
+/// One-sample Kolmogorov-Smirnov test of the hypothesis that
+/// `nums` come from the continuous distribution `dist`. The
+/// statistic is the greatest distance between the empirical
+/// distribution function of `nums` and the distribution
+/// function of `dist`.
+///
+/// The p-value is exact if there are fewer than 100 values
+/// and no ties. Otherwise it is from Kolmogorov's limiting
+/// distribution. The parameters of `dist` must not have been
+/// estimated from `nums`, or the p-value is too large. The
+/// test is undefined for empty input.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// # use stats::distributions::Normal;
+/// let nums = [-1.2, -0.6, -0.3, 0.1, 0.4, 0.7, 1.1, 1.9];
+/// let test = kolmogorov_smirnov(&nums, &Normal::standard()).unwrap();
+/// assert!((test.statistic - 0.1648278).abs() < 1e-7);
+/// assert!((test.p_value - 0.9575187).abs() < 1e-7);
+/// ```
+/// ```
+/// # use stats::*;
+/// # use stats::distributions::Normal;
+/// // A sample far from the standard normal.
+/// let nums = [5.0, 6.0, 7.0];
+/// let test = kolmogorov_smirnov(&nums, &Normal::standard()).unwrap();
+/// assert!(test.p_value < 1e-5);
+/// ```
+pub fn kolmogorov_smirnov(nums: &[f64], dist: &dyn Distribution) -> Option<TestResult> {
+    try_kolmogorov_smirnov(nums, dist).ok()
+}
+
+/// One-sample Kolmogorov-Smirnov test, or why it is
+/// undefined.
+pub(crate) fn try_kolmogorov_smirnov(
+    nums: &[f64],
+    dist: &dyn Distribution,
+) -> Result<TestResult, StatsError> {
+    StatsError::check_len(nums.len(), 1)?;
+    if nums.iter().any(|x| x.is_nan()) {
+        return Ok(TestResult::NAN);
+    }
+    let sorted = sorted(nums);
+    let n = sorted.len();
+    let nf = n as f64;
+    let d = sorted
+        .iter()
+        .enumerate()
+        .map(|(i, &x)| {
+            let cdf = dist.cdf(x);
+            (cdf - i as f64 / nf).max((i + 1) as f64 / nf - cdf)
+        })
+        .fold(0.0, f64::max);
+    let p_value = if n < EXACT_KS_SIZE && tie_correction(&sorted) == 0.0 {
+        1.0 - kolmogorov_cdf(n, d)
+    } else {
+        kolmogorov_sf(nf.sqrt() * d)
+    };
+    Ok(TestResult {
+        statistic: d,
+        p_value: p_value.clamp(0.0, 1.0),
+    })
+}
+
+/// Probability that the two-sample Kolmogorov-Smirnov
+/// statistic of samples of sizes `m` and `n` without ties is
+/// less than `d`: the proportion of the paths through the
+/// lattice of the combined sample in order along which the
+/// empirical distribution functions stay less than `d`
+/// apart.
+fn smirnov_cdf(m: usize, n: usize, d: f64) -> f64 {
+    let (m, n) = if m > n { (n, m) } else { (m, n) };
+    let (mf, nf) = (m as f64, n as f64);
+    // Allow for rounding in the statistic, which is a
+    // multiple of 1 / (m n).
+    let q = (0.5 + (d * mf * nf - 1e-7).floor()) / (mf * nf);
+    let mut u: Vec<f64> = (0..=n)
+        .map(|j| if j as f64 / nf > q { 0.0 } else { 1.0 })
+        .collect();
+    for i in 1..=m {
+        let w = i as f64 / (i + n) as f64;
+        u[0] = if i as f64 / mf > q { 0.0 } else { w * u[0] };
+        for j in 1..=n {
+            u[j] = if (i as f64 / mf - j as f64 / nf).abs() > q {
+                0.0
+            } else {
+                w * u[j] + u[j - 1]
+            };
+        }
+    }
+    u[n]
+}
+
+/// Two-sample Kolmogorov-Smirnov test of the hypothesis that
+/// two independent samples come from the same continuous
+/// distribution. The statistic is the greatest distance
+/// between their empirical distribution functions, so the
+/// test is sensitive to any difference in the shapes of the
+/// distributions, not only in their locations.
+///
+/// The p-value is exact if the product of the sample sizes
+/// is less than 10,000 and there are no ties. Otherwise it is
+/// from Kolmogorov's limiting distribution, which is
+/// conservative in the presence of ties. The test is
+/// undefined if either sample is empty.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// let xs = [0.61, 0.29, 0.06, 0.59, -1.73, -0.74, 0.51, -0.56, 0.39, 1.64, 0.05, -0.06];
+/// let ys = [1.63, 2.27, 1.25, 0.96, 1.40, 0.77, 1.28, 1.92, 0.64, 1.58];
+/// let test = kolmogorov_smirnov_two_sample(&xs, &ys).unwrap();
+/// assert!((test.statistic - 11.0 / 12.0).abs() < 1e-15);
+/// // 22 of the 646,646 orderings of the combined sample are as extreme.
+/// assert!((test.p_value - 22.0 / 646_646.0).abs() < 1e-15);
+/// ```
+pub fn kolmogorov_smirnov_two_sample(xs: &[f64], ys: &[f64]) -> Option<TestResult> {
+    try_kolmogorov_smirnov_two_sample(xs, ys).ok()
+}
+
+/// Two-sample Kolmogorov-Smirnov test, or why it is
+/// undefined.
+pub(crate) fn try_kolmogorov_smirnov_two_sample(
+    xs: &[f64],
+    ys: &[f64],
+) -> Result<TestResult, StatsError> {
+    StatsError::check_len(xs.len(), 1)?;
+    StatsError::check_len(ys.len(), 1)?;
+    if xs.iter().chain(ys).any(|x| x.is_nan()) {
+        return Ok(TestResult::NAN);
+    }
+    let (xs, ys) = (sorted(xs), sorted(ys));
+    let (m, n) = (xs.len(), ys.len());
+    let (mf, nf) = (m as f64, n as f64);
+    // Walk both samples in order, comparing the empirical
+    // distribution functions after each distinct value.
+    let (mut i, mut j, mut d) = (0, 0, 0.0f64);
+    while i < m && j < n {
+        let x = xs[i].min(ys[j]);
+        while i < m && xs[i] == x {
+            i += 1;
+        }
+        while j < n && ys[j] == x {
+            j += 1;
+        }
+        d = d.max((i as f64 / mf - j as f64 / nf).abs());
+    }
+    let combined: Vec<f64> = sorted(&[xs, ys].concat());
+    let p_value = if m * n < EXACT_KS_PRODUCT && tie_correction(&combined) == 0.0 {
+        1.0 - smirnov_cdf(m, n, d)
+    } else {
+        kolmogorov_sf((mf * nf / (mf + nf)).sqrt() * d)
+    };
+    Ok(TestResult {
+        statistic: d,
+        p_value: p_value.clamp(0.0, 1.0),
+    })
+}
@@ -0,0 +1,295 @@
+// Copyright © 2019 Bader Alshaya
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Tests of the hypothesis that a sample comes from a normal
+//! distribution, of unknown mean and variance. A small
+//! p-value is evidence that the sample is not normal; a
+//! large one only means that the sample is too small or too
+//! close to normal to tell. A NaN value makes the whole
+//! result NaN.
+
+use crate::select::compare;
+use crate::special::{ln_normal_cdf, normal_cdf, normal_quantile};
+use crate::{kurtosis, mean, sample_stddev, skewness, StatsError, TestResult};
+
+/// Value of the polynomial with coefficients `coefficients`,
+/// from the constant term up, at `x`.
+fn polynomial(coefficients: &[f64], x: f64) -> f64 {
+    coefficients.iter().rev().fold(0.0, |sum, c| sum * x + c)
+}
+
+/// Sorted copy of `nums`, which must not contain NaN.
+fn sorted(nums: &[f64]) -> Vec<f64> {
+    let mut sorted = nums.to_owned();
+    sorted.sort_unstable_by(compare);
+    sorted
+}
+
+/// Shapiro-Wilk test of normality. The statistic W is the
+/// squared correlation between the sorted values and the
+/// expected order statistics of a normal sample, so it is
+/// near 1 for normal data and smaller otherwise. The
+/// coefficients and the p-value are computed by Royston's
+/// approximations, "Remark AS R94" (1995), as in R's
+/// `shapiro.test`; they are calibrated for 3 to 5000 values.
+/// The test is undefined for fewer than three values, or if
+/// they are all equal.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// // Weights of eleven men, from Shapiro and Wilk (1965).
+/// let weights = [148.0, 154.0, 158.0, 160.0, 161.0, 162.0, 166.0, 170.0, 182.0, 195.0, 236.0];
+/// let test = shapiro_wilk(&weights).unwrap();
+/// assert!((test.statistic - 0.7888147).abs() < 1e-7);
+/// assert!((test.p_value - 0.006703814).abs() < 1e-9);
+/// ```
+/// ```
+/// # use stats::*;
+/// // Evenly spaced values look normal enough.
+/// let nums: Vec<f64> = (1..=20).map(f64::from).collect();
+/// let test = shapiro_wilk(&nums).unwrap();
+/// assert!((test.statistic - 0.9603752).abs() < 1e-7);
+/// assert!((test.p_value - 0.5513717).abs() < 1e-7);
+/// assert_eq!(None, shapiro_wilk(&[1.0, 2.0]));
+/// assert_eq!(None, shapiro_wilk(&[3.0, 3.0, 3.0]));
+/// ```
+pub fn shapiro_wilk(nums: &[f64]) -> Option<TestResult> {
+    try_shapiro_wilk(nums).ok()
+}
+
+/// Shapiro-Wilk test, or why it is undefined.
+pub(crate) fn try_shapiro_wilk(nums: &[f64]) -> Result<TestResult, StatsError> {
+    StatsError::check_len(nums.len(), 3)?;
+    if nums.iter().any(|x| x.is_nan()) {
+        return Ok(TestResult::NAN);
+    }
+    let x = sorted(nums);
+    let n = x.len();
+    let nf = n as f64;
+    if x[0] == x[n - 1] {
+        return Err(StatsError::ZeroVariance);
+    }
+
+    // Coefficients a[i] of the lower half of the sorted
+    // values, whose mirror images weight the upper half.
+    let half = n / 2;
+    let mut a = vec![0.0; half];
+    if n == 3 {
+        a[0] = 0.5f64.sqrt();
+    } else {
+        let m: Vec<f64> = (1..=half)
+            .map(|i| normal_quantile((i as f64 - 0.375) / (nf + 0.25)))
+            .collect();
+        let summ2 = 2.0 * m.iter().map(|m| m * m).sum::<f64>();
+        let ssumm2 = summ2.sqrt();
+        let rsn = 1.0 / nf.sqrt();
+        const C1: [f64; 6] = [0.0, 0.221157, -0.147981, -2.07119, 4.434685, -2.706056];
+        const C2: [f64; 6] = [0.0, 0.042981, -0.293762, -1.752461, 5.682633, -3.582633];
+        let a1 = polynomial(&C1, rsn) - m[0] / ssumm2;
+        let (first, fac) = if n > 5 {
+            let a2 = -m[1] / ssumm2 + polynomial(&C2, rsn);
+            a[1] = a2;
+            let fac = ((summ2 - 2.0 * m[0] * m[0] - 2.0 * m[1] * m[1])
+                / (1.0 - 2.0 * a1 * a1 - 2.0 * a2 * a2))
+                .sqrt();
+            (2, fac)
+        } else {
+            let fac = ((summ2 - 2.0 * m[0] * m[0]) / (1.0 - 2.0 * a1 * a1)).sqrt();
+            (1, fac)
+        };
+        a[0] = a1;
+        for i in first..half {
+            a[i] = -m[i] / fac;
+        }
+    }
+
+    // W is the squared correlation of the values with the
+    // coefficients, which sum to zero. Computing 1 - W
+    // directly keeps its precision when W is near 1.
+    let weight = |i: usize| {
+        if i < half {
+            -a[i]
+        } else if n - 1 - i < half {
+            a[n - 1 - i]
+        } else {
+            0.0
+        }
+    };
+    let mean = mean(&x).unwrap();
+    let (mut ssa, mut ssx, mut sax) = (0.0, 0.0, 0.0);
+    for (i, &xi) in x.iter().enumerate() {
+        let (ai, di) = (weight(i), xi - mean);
+        ssa += ai * ai;
+        ssx += di * di;
+        sax += ai * di;
+    }
+    let root = (ssa * ssx).sqrt();
+    let w1 = (root - sax) * (root + sax) / (ssa * ssx);
+    let w = 1.0 - w1;
+
+    let p_value = if n == 3 {
+        // The exact distribution, from the arcsine of √W.
+        let p = 6.0 / std::f64::consts::PI * (w.sqrt().asin() - std::f64::consts::PI / 3.0);
+        p.clamp(0.0, 1.0)
+    } else {
+        // ln(1 - W) is roughly normal, after a further
+        // transformation for small samples.
+        let y = w1.ln();
+        let (y, m, s) = if n <= 11 {
+            let gamma = polynomial(&[-2.273, 0.459], nf);
+            if y >= gamma {
+                // Beyond the range of the approximation.
+                return Ok(TestResult {
+                    statistic: w,
+                    p_value: 1e-99,
+                });
+            }
+            const C3: [f64; 4] = [0.544, -0.39978, 0.025054, -6.714e-4];
+            const C4: [f64; 4] = [1.3822, -0.77857, 0.062767, -0.0020322];
+            (
+                -(gamma - y).ln(),
+                polynomial(&C3, nf),
+                polynomial(&C4, nf).exp(),
+            )
+        } else {
+            const C5: [f64; 4] = [-1.5861, -0.31082, -0.083751, 0.0038915];
+            const C6: [f64; 3] = [-0.4803, -0.082676, 0.0030302];
+            let ln_n = nf.ln();
+            (y, polynomial(&C5, ln_n), polynomial(&C6, ln_n).exp())
+        };
+        normal_cdf((m - y) / s)
+    };
+    Ok(TestResult {
+        statistic: w,
+        p_value,
+    })
+}
+
+/// Anderson-Darling test of normality, with the mean and
+/// standard deviation estimated from the sample. The
+/// statistic A² measures the distance between the empirical
+/// distribution function of the values and the fitted
+/// normal one, weighting the tails more heavily than the
+/// Kolmogorov-Smirnov statistic does. The p-value is from
+/// the approximations of D'Agostino and Stephens, "Goodness-
+/// of-Fit Techniques" (1986), to the statistic corrected for
+/// sample size, as in R's `nortest::ad.test`. The test is
+/// undefined for fewer than eight values, or if they are
+/// all equal.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// let nums = [2.1, 2.4, 2.6, 2.8, 3.0, 3.1, 3.3, 3.6, 3.9, 4.4];
+/// let test = anderson_darling(&nums).unwrap();
+/// assert!((test.statistic - 0.1260556).abs() < 1e-7);
+/// assert!((test.p_value - 0.9759059).abs() < 1e-7);
+/// ```
+/// ```
+/// # use stats::*;
+/// // Skewed data.
+/// let nums = [1.0, 1.0, 1.0, 1.0, 1.0, 2.0, 2.0, 3.0, 5.0, 9.0, 17.0, 40.0];
+/// assert!(anderson_darling(&nums).unwrap().p_value < 0.001);
+/// ```
+/// ```
+/// # use stats::*;
+/// // An outlier 45 standard deviations out.
+/// let mut nums: Vec<f64> = (0..1999).map(|i| f64::from(i % 10)).collect();
+/// nums.push(1e6);
+/// let test = anderson_darling(&nums).unwrap();
+/// assert!((test.statistic - 772.0697395289656).abs() < 1e-9);
+/// ```
+pub fn anderson_darling(nums: &[f64]) -> Option<TestResult> {
+    try_anderson_darling(nums).ok()
+}
+
+/// Anderson-Darling test, or why it is undefined.
+pub(crate) fn try_anderson_darling(nums: &[f64]) -> Result<TestResult, StatsError> {
+    StatsError::check_len(nums.len(), 8)?;
+    if nums.iter().any(|x| x.is_nan()) {
+        return Ok(TestResult::NAN);
+    }
+    let x = sorted(nums);
+    let nf = x.len() as f64;
+    let (mean, sd) = (mean(&x).unwrap(), sample_stddev(&x).unwrap());
+    if sd == 0.0 {
+        return Err(StatsError::ZeroVariance);
+    }
+    let z: Vec<f64> = x.iter().map(|xi| (xi - mean) / sd).collect();
+    // Both tails as lower ones, in logarithms, so that an
+    // outlier far in either tail does not underflow.
+    let sum: f64 = z
+        .iter()
+        .zip(z.iter().rev())
+        .enumerate()
+        .map(|(i, (lower, upper))| {
+            (2.0 * i as f64 + 1.0) * (ln_normal_cdf(*lower) + ln_normal_cdf(-upper))
+        })
+        .sum();
+    let a = -nf - sum / nf;
+    let aa = a * (1.0 + 0.75 / nf + 2.25 / (nf * nf));
+    let p_value = if aa < 0.2 {
+        -(-13.436 + 101.14 * aa - 223.73 * aa * aa).exp_m1()
+    } else if aa < 0.34 {
+        -(-8.318 + 42.796 * aa - 59.938 * aa * aa).exp_m1()
+    } else if aa < 0.6 {
+        (0.9177 - 4.279 * aa - 1.38 * aa * aa).exp()
+    } else if aa < 10.0 {
+        (1.2937 - 5.709 * aa + 0.0186 * aa * aa).exp()
+    } else {
+        // Beyond the range of the approximation.
+        3.7e-24
+    };
+    Ok(TestResult {
+        statistic: a,
+        p_value: p_value.clamp(0.0, 1.0),
+    })
+}
+
+/// Jarque-Bera test of normality, from the skewness S and
+/// the excess kurtosis K of the sample, both of which are
+/// zero for a normal distribution. The statistic is
+/// `n (S² + K²/4) / 6`, whose distribution approaches
+/// chi-squared with two degrees of freedom for large
+/// samples; the p-value is from that, and is unreliable for
+/// fewer than a few hundred values. The test is undefined
+/// for fewer than two values, or if they are all equal.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// // Weights of eleven men, from Shapiro and Wilk (1965).
+/// let weights = [148.0, 154.0, 158.0, 160.0, 161.0, 162.0, 166.0, 170.0, 182.0, 195.0, 236.0];
+/// let test = jarque_bera(&weights).unwrap();
+/// assert!((test.statistic - 6.982848237344645).abs() < 1e-12);
+/// assert!((test.p_value - 0.03045746622458190).abs() < 1e-14);
+/// assert_eq!(None, jarque_bera(&[3.0, 3.0]));
+/// ```
+pub fn jarque_bera(nums: &[f64]) -> Option<TestResult> {
+    try_jarque_bera(nums).ok()
+}
+
+/// Jarque-Bera test, or why it is undefined.
+pub(crate) fn try_jarque_bera(nums: &[f64]) -> Result<TestResult, StatsError> {
+    StatsError::check_len(nums.len(), 2)?;
+    if nums.iter().any(|x| x.is_nan()) {
+        return Ok(TestResult::NAN);
+    }
+    let (s, k) = match (skewness(nums), kurtosis(nums)) {
+        (Some(s), Some(k)) => (s, k),
+        _ => return Err(StatsError::ZeroVariance),
+    };
+    let statistic = nums.len() as f64 / 6.0 * (s * s + k * k / 4.0);
+    // The chi-squared survival function with two degrees of
+    // freedom.
+    Ok(TestResult {
+        statistic,
+        p_value: (-statistic / 2.0).exp(),
+    })
+}
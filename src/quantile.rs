@@ -0,0 +1,278 @@
+// Copyright © 2019 Bader Alshaya
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Quantiles and percentiles, with the interpolation
+//! methods of Hyndman and Fan ("Sample Quantiles in
+//! Statistical Packages", 1996) under the names numpy uses
+//! for them.
+
+use crate::select::{compare, select_in_place};
+
+/// How a quantile that falls between two input values is
+/// computed. The first five are numpy's traditional
+/// methods; the rest are the nine types of Hyndman and
+/// Fan. Below, `n` is the number of values, `p` the
+/// requested probability, and `x[i]` the `i`-th smallest
+/// value counting from 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum QuantileMethod {
+    /// Interpolate linearly at position `p * (n - 1)`. This
+    /// is Hyndman and Fan type 7, and the default in numpy
+    /// and R.
+    #[default]
+    Linear,
+    /// The value below position `p * (n - 1)`.
+    Lower,
+    /// The value above position `p * (n - 1)`.
+    Higher,
+    /// The value nearest position `p * (n - 1)`, rounding
+    /// half-way positions to the even index.
+    Nearest,
+    /// The average of the values below and above position
+    /// `p * (n - 1)`.
+    Midpoint,
+    /// Type 1: the inverse of the empirical distribution
+    /// function.
+    InvertedCdf,
+    /// Type 2: as type 1, but averaging at discontinuities.
+    AveragedInvertedCdf,
+    /// Type 3: the nearest even order statistic (SAS
+    /// definition 2).
+    ClosestObservation,
+    /// Type 4: linear interpolation of the empirical
+    /// distribution function.
+    InterpolatedInvertedCdf,
+    /// Type 5: piecewise linear, with the knots at the
+    /// midpoints of the steps of the empirical distribution
+    /// function.
+    Hazen,
+    /// Type 6: interpolate at position `p * (n + 1) - 1`
+    /// (Minitab, SPSS).
+    Weibull,
+    /// Type 8: approximately median-unbiased regardless of
+    /// the distribution; recommended by Hyndman and Fan.
+    MedianUnbiased,
+    /// Type 9: approximately unbiased for normally
+    /// distributed values.
+    NormalUnbiased,
+}
+
+/// Where a quantile lies among the sorted values: the
+/// weighted average of the values at indices `lower` and
+/// `lower + 1` (or just `lower`, if `weight` is 0).
+#[derive(Debug, Clone, Copy)]
+struct Position {
+    lower: usize,
+    weight: f64,
+}
+
+impl QuantileMethod {
+    /// Position of the `p` quantile of `n` values, which
+    /// must both be valid.
+    fn position(self, n: usize, p: f64) -> Position {
+        use QuantileMethod::*;
+        let last = (n - 1) as f64;
+        let nf = n as f64;
+        // Virtual index, counting from 0, for the continuous
+        // types; see Hyndman and Fan, section 3.
+        let continuous = |alpha: f64, beta: f64| {
+            let m = alpha + p * (1.0 - alpha - beta);
+            nf * p + m - 1.0
+        };
+        let (index, discrete) = match self {
+            Linear => (p * last, false),
+            Lower => ((p * last).floor(), true),
+            Higher => ((p * last).ceil(), true),
+            Nearest => ((p * last).round_ties_even(), true),
+            Midpoint => {
+                let index = (p * last).floor();
+                let weight = if (p * last).fract() > 0.0 { 0.5 } else { 0.0 };
+                return Position {
+                    lower: index as usize,
+                    weight,
+                };
+            }
+            InvertedCdf => ((nf * p).ceil() - 1.0, true),
+            AveragedInvertedCdf => {
+                let np = nf * p;
+                if np.fract() == 0.0 && np > 0.0 && np < nf {
+                    return Position {
+                        lower: np as usize - 1,
+                        weight: 0.5,
+                    };
+                }
+                (np.ceil() - 1.0, true)
+            }
+            ClosestObservation => {
+                // With j = floor(np - 1/2), counting from 1:
+                // x[j] if np - 1/2 is exactly the even j,
+                // otherwise x[j + 1].
+                let h = nf * p - 0.5;
+                let j = h.floor();
+                if h == j && j % 2.0 == 0.0 {
+                    (j - 1.0, true)
+                } else {
+                    (j, true)
+                }
+            }
+            InterpolatedInvertedCdf => (continuous(0.0, 1.0), false),
+            Hazen => (continuous(0.5, 0.5), false),
+            Weibull => (continuous(0.0, 0.0), false),
+            MedianUnbiased => (continuous(1.0 / 3.0, 1.0 / 3.0), false),
+            NormalUnbiased => (continuous(3.0 / 8.0, 3.0 / 8.0), false),
+        };
+        let index = index.max(0.0).min(last);
+        let lower = index.floor();
+        let weight = if discrete { 0.0 } else { index - lower };
+        Position {
+            lower: lower as usize,
+            weight,
+        }
+    }
+}
+
+/// Linear interpolation from `a` (at `t = 0`) to `b` (at
+/// `t = 1`), exact at both ends.
+pub(crate) fn lerp(a: f64, b: f64, t: f64) -> f64 {
+    if t == 0.0 || a == b {
+        a
+    } else if t < 0.5 {
+        a + (b - a) * t
+    } else {
+        b - (b - a) * (1.0 - t)
+    }
+}
+
+/// Whether `p` is a valid probability.
+fn valid(p: f64) -> bool {
+    (0.0..=1.0).contains(&p)
+}
+
+/// The `p` quantile of already sorted input values, for `p`
+/// between 0 and 1, computed with `method`. This is the
+/// cheapest way to compute several quantiles of the same
+/// values. The quantile of an empty list, or for `p` outside
+/// `[0, 1]`, is undefined.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// let sorted = [1.0, 2.0, 3.0, 4.0];
+/// assert_eq!(Some(1.75), quantile_sorted(&sorted, 0.25, QuantileMethod::Linear));
+/// assert_eq!(Some(1.0), quantile_sorted(&sorted, 0.25, QuantileMethod::Lower));
+/// assert_eq!(Some(2.0), quantile_sorted(&sorted, 0.25, QuantileMethod::Higher));
+/// assert_eq!(Some(2.0), quantile_sorted(&sorted, 0.25, QuantileMethod::Nearest));
+/// assert_eq!(Some(1.5), quantile_sorted(&sorted, 0.25, QuantileMethod::Midpoint));
+/// ```
+/// ```
+/// # use stats::*;
+/// // Hyndman and Fan types 1 through 9, as computed by R's
+/// // `quantile(1:10, 0.3, type = t)`.
+/// let sorted: Vec<f64> = (1..=10).map(f64::from).collect();
+/// let q = |method| quantile_sorted(&sorted, 0.3, method).unwrap();
+/// assert_eq!(3.0, q(QuantileMethod::InvertedCdf));
+/// assert_eq!(3.5, q(QuantileMethod::AveragedInvertedCdf));
+/// assert_eq!(3.0, q(QuantileMethod::ClosestObservation));
+/// assert!((q(QuantileMethod::InterpolatedInvertedCdf) - 3.0).abs() < 1e-12);
+/// assert!((q(QuantileMethod::Hazen) - 3.5).abs() < 1e-12);
+/// assert!((q(QuantileMethod::Weibull) - 3.3).abs() < 1e-12);
+/// assert!((q(QuantileMethod::Linear) - 3.7).abs() < 1e-12);
+/// assert!((q(QuantileMethod::MedianUnbiased) - 3.433333333333333).abs() < 1e-12);
+/// assert!((q(QuantileMethod::NormalUnbiased) - 3.45).abs() < 1e-12);
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, quantile_sorted(&[], 0.5, QuantileMethod::Linear));
+/// assert_eq!(None, quantile_sorted(&[1.0], 1.5, QuantileMethod::Linear));
+/// ```
+pub fn quantile_sorted(sorted: &[f64], p: f64, method: QuantileMethod) -> Option<f64> {
+    if sorted.is_empty() || !valid(p) {
+        return None;
+    }
+    let Position { lower, weight } = method.position(sorted.len(), p);
+    if weight == 0.0 {
+        Some(sorted[lower])
+    } else {
+        Some(lerp(sorted[lower], sorted[lower + 1], weight))
+    }
+}
+
+/// The `p` quantile of the input values, for `p` between 0
+/// and 1, computed with `method`. The values needed are
+/// found by selection, as for [`median_with`](crate::median_with),
+/// in expected linear time. The quantile of an empty list,
+/// or for `p` outside `[0, 1]`, is undefined; the quantile
+/// of values including a NaN is NaN.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// let nums = [4.0, 1.0, 3.0, 2.0, 5.0];
+/// assert_eq!(Some(4.6), quantile(&nums, 0.9, QuantileMethod::Linear));
+/// ```
+/// ```
+/// # use stats::*;
+/// let nums = [0.0, 0.5, -1.0, 1.0];
+/// assert_eq!(quantile(&nums, 0.5, QuantileMethod::Lower), median(&nums));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, quantile(&[1.0, 2.0], -0.1, QuantileMethod::Linear));
+/// ```
+pub fn quantile(nums: &[f64], p: f64, method: QuantileMethod) -> Option<f64> {
+    if nums.is_empty() || !valid(p) {
+        return None;
+    }
+    let mut nums = nums.to_owned();
+    let Position { lower, weight } = method.position(nums.len(), p);
+    let below = select_in_place(&mut nums, lower)?;
+    if weight == 0.0 || below.is_nan() {
+        return Some(below);
+    }
+    // Selection leaves the next value as the smallest of the
+    // values after the one selected.
+    let above = nums[lower + 1..]
+        .iter()
+        .copied()
+        .fold(f64::INFINITY, f64::min);
+    Some(lerp(below, above, weight))
+}
+
+/// Several quantiles of the input values, as for
+/// [`quantile`], computed from a single sorted copy of the
+/// input. The result is undefined if the input is empty or
+/// any of `ps` is outside `[0, 1]`.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// let nums: Vec<f64> = (1..=100).map(f64::from).collect();
+/// let qs = quantiles(&nums, &[0.5, 0.9, 0.99], QuantileMethod::Nearest).unwrap();
+/// assert_eq!(vec![51.0, 90.0, 99.0], qs);
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(vec![]), quantiles(&[1.0], &[], QuantileMethod::Linear));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, quantiles(&[1.0], &[0.5, 2.0], QuantileMethod::Linear));
+/// ```
+pub fn quantiles(nums: &[f64], ps: &[f64], method: QuantileMethod) -> Option<Vec<f64>> {
+    if nums.is_empty() || !ps.iter().all(|&p| valid(p)) {
+        return None;
+    }
+    if nums.iter().any(|x| x.is_nan()) {
+        return Some(vec![f64::NAN; ps.len()]);
+    }
+    let mut sorted = nums.to_owned();
+    sorted.sort_unstable_by(compare);
+    ps.iter()
+        .map(|&p| quantile_sorted(&sorted, p, method))
+        .collect()
+}
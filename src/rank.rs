@@ -0,0 +1,45 @@
+// Copyright © 2019 Bader Alshaya
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Ranks of input values, for the rank-based statistics.
+
+use crate::select::compare;
+
+/// Ranks of the input values, in input order: 1 for the
+/// smallest value up to `n` for the largest, with tied
+/// values all given the average of the ranks they span. If
+/// any value is NaN, all ranks are NaN.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(vec![3.0, 1.0, 2.0], ranks(&[30.0, 10.0, 20.0]));
+/// assert_eq!(vec![1.0, 2.5, 2.5, 4.0], ranks(&[1.0, 5.0, 5.0, 7.0]));
+/// ```
+pub fn ranks(nums: &[f64]) -> Vec<f64> {
+    if nums.iter().any(|x| x.is_nan()) {
+        return vec![f64::NAN; nums.len()];
+    }
+    let mut order: Vec<usize> = (0..nums.len()).collect();
+    order.sort_unstable_by(|&i, &j| compare(&nums[i], &nums[j]));
+    let mut ranks = vec![0.0; nums.len()];
+    let mut start = 0;
+    for run in order.chunk_by(|&i, &j| nums[i] == nums[j]) {
+        // Average of the ranks start + 1 to start + run.len().
+        let rank = start as f64 + (run.len() as f64 + 1.0) / 2.0;
+        for &i in run {
+            ranks[i] = rank;
+        }
+        start += run.len();
+    }
+    ranks
+}
+
+/// Sizes of the groups of tied values in `sorted`, which
+/// must be in order, including groups of one.
+pub(crate) fn tie_sizes(sorted: &[f64]) -> impl Iterator<Item = usize> + '_ {
+    sorted.chunk_by(|a, b| a == b).map(<[f64]>::len)
+}
@@ -0,0 +1,348 @@
+// Copyright © 2019 Bader Alshaya
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Ordinary least squares regression of a response variable
+//! on one or more predictor variables, each given as a slice
+//! of values with one value per observation. The fit is
+//! undefined if the lengths differ. A NaN value makes the
+//! whole fit NaN.
+
+use crate::accumulator::CompensatedSum;
+use crate::special::t_two_sided;
+use crate::StatsError;
+
+/// Relative size, against the norm of its centered column,
+/// below which a predictor is taken to be a linear
+/// combination of the ones before it. This is the tolerance
+/// R uses for `lm`.
+const COLLINEARITY_TOLERANCE: f64 = 1e-7;
+
+/// Least squares fit of a linear model, with its
+/// diagnostics.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Regression {
+    /// The intercept, followed by the coefficient of each
+    /// predictor in order.
+    pub coefficients: Vec<f64>,
+    /// Standard error of each coefficient.
+    pub standard_errors: Vec<f64>,
+    /// Each coefficient divided by its standard error.
+    pub t_statistics: Vec<f64>,
+    /// Two-sided p-value of each t statistic, for the
+    /// hypothesis that the coefficient is zero.
+    pub p_values: Vec<f64>,
+    /// Coefficient of determination: the proportion of the
+    /// variance of the response that the fit explains.
+    pub r_squared: f64,
+    /// R² adjusted for the number of predictors, which only
+    /// grows if a predictor improves the fit by more than
+    /// chance would.
+    pub adjusted_r_squared: f64,
+    /// Estimated standard deviation of the errors.
+    pub residual_standard_error: f64,
+    /// Number of observations less the number of
+    /// coefficients.
+    pub degrees_of_freedom: usize,
+    /// Each observed response less its fitted value.
+    pub residuals: Vec<f64>,
+}
+
+impl Regression {
+    /// Fit of `n` observations on `k` predictors that
+    /// include a NaN.
+    fn nan(n: usize, k: usize) -> Regression {
+        Regression {
+            coefficients: vec![f64::NAN; k + 1],
+            standard_errors: vec![f64::NAN; k + 1],
+            t_statistics: vec![f64::NAN; k + 1],
+            p_values: vec![f64::NAN; k + 1],
+            r_squared: f64::NAN,
+            adjusted_r_squared: f64::NAN,
+            residual_standard_error: f64::NAN,
+            degrees_of_freedom: n - k - 1,
+            residuals: vec![f64::NAN; n],
+        }
+    }
+
+    /// The intercept.
+    pub fn intercept(&self) -> f64 {
+        self.coefficients[0]
+    }
+
+    /// The coefficient of the first predictor, which is the
+    /// slope of a simple regression. Panics if there are no
+    /// predictors.
+    pub fn slope(&self) -> f64 {
+        self.coefficients[1]
+    }
+
+    /// The fitted response for the predictor values `x`, one
+    /// per predictor. Panics if `x` has the wrong length.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use stats::*;
+    /// let fit = simple_regression(&[1.0, 2.0, 3.0], &[3.0, 5.0, 7.0]).unwrap();
+    /// assert!((fit.predict(&[10.0]) - 21.0).abs() < 1e-12);
+    /// ```
+    pub fn predict(&self, x: &[f64]) -> f64 {
+        assert_eq!(self.coefficients.len() - 1, x.len());
+        let mut sum = CompensatedSum::default();
+        sum.add(self.coefficients[0]);
+        for (b, x) in self.coefficients[1..].iter().zip(x) {
+            sum.add(b * x);
+        }
+        sum.value()
+    }
+}
+
+/// Mean of `nums`, which are not empty.
+fn mean_of(nums: &[f64]) -> f64 {
+    let mut sum = CompensatedSum::default();
+    for &x in nums {
+        sum.add(x);
+    }
+    sum.value() / nums.len() as f64
+}
+
+/// Sum of the squares of `nums`.
+fn sum_squares(nums: &[f64]) -> f64 {
+    let mut sum = CompensatedSum::default();
+    for &x in nums {
+        sum.add(x * x);
+    }
+    sum.value()
+}
+
+/// Simple linear regression of `ys` on `xs`, fitting the
+/// line `y = intercept + slope × x`. See
+/// [`multiple_regression`], of which this is the case of one
+/// predictor.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// let xs = [1.0, 2.0, 3.0, 4.0, 5.0];
+/// let ys = [2.0, 4.0, 5.0, 4.0, 5.0];
+/// let fit = simple_regression(&xs, &ys).unwrap();
+/// assert!((fit.intercept() - 2.2).abs() < 1e-12);
+/// assert!((fit.slope() - 0.6).abs() < 1e-12);
+/// assert!((fit.r_squared - 0.6).abs() < 1e-12);
+/// assert!((fit.standard_errors[1] - 0.282842712474619).abs() < 1e-12);
+/// assert!((fit.p_values[1] - 0.12402706265755463).abs() < 1e-12);
+/// assert_eq!(3, fit.degrees_of_freedom);
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, simple_regression(&[1.0, 2.0], &[1.0, 2.0]));
+/// assert_eq!(None, simple_regression(&[1.0, 1.0, 1.0], &[1.0, 2.0, 3.0]));
+/// ```
+pub fn simple_regression(xs: &[f64], ys: &[f64]) -> Option<Regression> {
+    try_multiple_regression(&[xs], ys).ok()
+}
+
+/// Multiple linear regression of `ys` on the `predictors`,
+/// fitting `y = b0 + b1 × x1 + … + bk × xk` by least squares.
+/// The predictors and the response are centered on their
+/// means, which fits the intercept exactly and improves the
+/// conditioning, and the centered predictors are reduced by
+/// Householder QR decomposition. This avoids forming the
+/// normal equations, which would square the condition number
+/// and so lose twice as many digits to nearly collinear
+/// predictors.
+///
+/// The fit is undefined unless there are more observations
+/// than coefficients, if any predictor has all values
+/// equal, or if a predictor is a linear combination of the
+/// others. A response with all values equal is fitted
+/// exactly, with every slope zero; as it has no variance to
+/// explain, R² and adjusted R² are then NaN, as are the t
+/// statistics and p-values of the slopes.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// // y = 1 + 2 x1 - x2, exactly.
+/// let x1 = [0.0, 1.0, 2.0, 0.0, 1.0];
+/// let x2 = [0.0, 0.0, 1.0, 1.0, 3.0];
+/// let ys = [1.0, 3.0, 4.0, 0.0, 0.0];
+/// let fit = multiple_regression(&[&x1, &x2], &ys).unwrap();
+/// for (b, expected) in fit.coefficients.iter().zip([1.0, 2.0, -1.0]) {
+///     assert!((b - expected).abs() < 1e-12);
+/// }
+/// assert!((fit.r_squared - 1.0).abs() < 1e-12);
+/// ```
+/// ```
+/// # use stats::*;
+/// // The second predictor is twice the first.
+/// let x1 = [1.0, 2.0, 3.0, 4.0];
+/// let x2 = [2.0, 4.0, 6.0, 8.0];
+/// assert_eq!(None, multiple_regression(&[&x1, &x2], &[1.0, 3.0, 2.0, 4.0]));
+/// ```
+/// ```
+/// # use stats::*;
+/// // A flat response.
+/// let fit = simple_regression(&[1.0, 2.0, 3.0], &[5.0, 5.0, 5.0]).unwrap();
+/// assert_eq!(vec![5.0, 0.0], fit.coefficients);
+/// assert_eq!(vec![0.0; 3], fit.residuals);
+/// assert!(fit.r_squared.is_nan() && fit.adjusted_r_squared.is_nan());
+/// assert!(fit.p_values[1].is_nan());
+/// ```
+pub fn multiple_regression(predictors: &[&[f64]], ys: &[f64]) -> Option<Regression> {
+    try_multiple_regression(predictors, ys).ok()
+}
+
+/// Multiple linear regression, or why it is undefined.
+pub(crate) fn try_multiple_regression(
+    predictors: &[&[f64]],
+    ys: &[f64],
+) -> Result<Regression, StatsError> {
+    let (n, k) = (ys.len(), predictors.len());
+    if predictors.iter().any(|xs| xs.len() != n) {
+        return Err(StatsError::LengthMismatch);
+    }
+    StatsError::check_len(n, k + 2)?;
+    if ys
+        .iter()
+        .chain(predictors.iter().copied().flatten())
+        .any(|x| x.is_nan())
+    {
+        return Ok(Regression::nan(n, k));
+    }
+
+    let y_mean = mean_of(ys);
+    let x_means: Vec<f64> = predictors.iter().map(|xs| mean_of(xs)).collect();
+    let centered = |xs: &[f64], mean: f64| -> Vec<f64> { xs.iter().map(|&x| x - mean).collect() };
+    // The centered predictors, reduced in place to the upper
+    // triangle of R, and the centered response, transformed
+    // alongside them to Qᵀy.
+    let mut columns: Vec<Vec<f64>> = predictors
+        .iter()
+        .zip(&x_means)
+        .map(|(xs, &mean)| centered(xs, mean))
+        .collect();
+    let mut qty = centered(ys, y_mean);
+    let total_squares = sum_squares(&qty);
+    let norms: Vec<f64> = columns.iter().map(|c| crate::l2(c).unwrap()).collect();
+    if norms.contains(&0.0) {
+        return Err(StatsError::ZeroVariance);
+    }
+
+    // Householder QR: reflect each column onto the diagonal
+    // and apply the same reflection to the columns after it
+    // and to the response.
+    let mut diagonal = vec![0.0; k];
+    for j in 0..k {
+        let (done, rest) = columns.split_at_mut(j + 1);
+        let v = &mut done[j][j..];
+        let alpha = -crate::l2(v).unwrap().copysign(v[0]);
+        if alpha.abs() <= COLLINEARITY_TOLERANCE * norms[j] {
+            return Err(StatsError::Collinear);
+        }
+        diagonal[j] = alpha;
+        v[0] -= alpha;
+        let vv = sum_squares(v);
+        for a in rest.iter_mut().map(|c| &mut c[j..]).chain([&mut qty[j..]]) {
+            let s = 2.0 * v.iter().zip(a.iter()).map(|(v, a)| v * a).sum::<f64>() / vv;
+            for (a, v) in a.iter_mut().zip(v.iter()) {
+                *a -= s * v;
+            }
+        }
+    }
+    let r = |i: usize, j: usize| if i == j { diagonal[i] } else { columns[j][i] };
+
+    // Back substitution for the slopes, then the intercept
+    // from the means.
+    let mut slopes = vec![0.0; k];
+    for j in (0..k).rev() {
+        let s: f64 = qty[j] - (j + 1..k).map(|m| r(j, m) * slopes[m]).sum::<f64>();
+        slopes[j] = s / r(j, j);
+    }
+    let mut intercept = CompensatedSum::default();
+    intercept.add(y_mean);
+    for (b, mean) in slopes.iter().zip(&x_means) {
+        intercept.add(-b * mean);
+    }
+    let intercept = intercept.value();
+
+    let residuals: Vec<f64> = (0..n)
+        .map(|i| {
+            let mut e = CompensatedSum::default();
+            e.add(ys[i] - y_mean);
+            for ((xs, mean), b) in predictors.iter().zip(&x_means).zip(&slopes) {
+                e.add(-b * (xs[i] - mean));
+            }
+            e.value()
+        })
+        .collect();
+    let residual_squares = sum_squares(&residuals);
+    let df = n - k - 1;
+    let variance = residual_squares / df as f64;
+
+    // The covariance of the slopes is σ² R⁻¹R⁻ᵀ, so the
+    // variance of each is σ² times the squared norm of its
+    // row of R⁻¹. The intercept is the mean response less
+    // x̄ᵀb, with variance σ² (1/n + |R⁻ᵀx̄|²).
+    // Columns of R⁻¹, each down to the diagonal.
+    let r_inverse: Vec<Vec<f64>> = (0..k)
+        .map(|m| {
+            let mut column = vec![0.0; m + 1];
+            column[m] = 1.0 / r(m, m);
+            for j in (0..m).rev() {
+                let s: f64 = (j + 1..=m).map(|l| r(j, l) * column[l]).sum();
+                column[j] = -s / r(j, j);
+            }
+            column
+        })
+        .collect();
+    let w: Vec<f64> = r_inverse
+        .iter()
+        .map(|column| column.iter().zip(&x_means).map(|(r, x)| r * x).sum())
+        .collect();
+    let mut variances = vec![variance * (1.0 / n as f64 + sum_squares(&w))];
+    variances.extend((0..k).map(|j| {
+        let row: f64 = r_inverse[j..]
+            .iter()
+            .map(|column| column[j] * column[j])
+            .sum();
+        variance * row
+    }));
+
+    let mut coefficients = vec![intercept];
+    coefficients.extend(slopes);
+    let standard_errors: Vec<f64> = variances.iter().map(|v| v.sqrt()).collect();
+    let t_statistics: Vec<f64> = coefficients
+        .iter()
+        .zip(&standard_errors)
+        .map(|(b, se)| b / se)
+        .collect();
+    let p_values = t_statistics
+        .iter()
+        .map(|&t| t_two_sided(t, df as f64))
+        .collect();
+    // A constant response leaves no variance to explain.
+    let (r_squared, adjusted_r_squared) = if total_squares == 0.0 {
+        (f64::NAN, f64::NAN)
+    } else {
+        (
+            1.0 - residual_squares / total_squares,
+            1.0 - variance / (total_squares / (n - 1) as f64),
+        )
+    };
+    Ok(Regression {
+        coefficients,
+        standard_errors,
+        t_statistics,
+        p_values,
+        r_squared,
+        adjusted_r_squared,
+        residual_standard_error: variance.sqrt(),
+        degrees_of_freedom: df,
+        residuals,
+    })
+}
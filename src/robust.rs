@@ -0,0 +1,267 @@
+// Copyright © 2019 Bader Alshaya
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Robust estimators of location and scale, which a few wild
+//! values cannot throw off the way they throw off the mean
+//! and standard deviation. Like [`median`](crate::median),
+//! they are found by selection where possible rather than by
+//! sorting. Medians taken here are the conventional ones,
+//! averaging the two middle values of an even number of
+//! values.
+
+use crate::accumulator::{accumulate, CompensatedSum};
+use crate::select::trim_in_place;
+use crate::{median_in_place, Accumulator, MedianPolicy, QuantileMethod, RunningMean};
+
+/// Scale factor that makes the median absolute deviation a
+/// consistent estimator of the standard deviation of a
+/// normal distribution: 1/Φ⁻¹(3/4).
+const MAD_NORMAL_SCALE: f64 = 1.482602218505602;
+
+/// Number of values cut from each end of `n` values for a
+/// trimming `fraction`, if the fraction is valid.
+fn trim_count(n: usize, fraction: f64) -> Option<usize> {
+    if (0.0..0.5).contains(&fraction) {
+        Some((n as f64 * fraction) as usize)
+    } else {
+        None
+    }
+}
+
+/// Whether any input value is NaN.
+fn has_nan(nums: &[f64]) -> bool {
+    nums.iter().any(|x| x.is_nan())
+}
+
+/// Trimmed mean of input values: the mean of the values
+/// left after the smallest and largest `fraction` of them
+/// (rounded down) are dropped. A fraction of 0 gives the
+/// mean. The trimmed mean is undefined for an empty list, or
+/// unless `fraction` is at least 0 and below 0.5.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(3.0), trimmed_mean(&[1.0, 2.0, 3.0, 4.0, 100.0], 0.2));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(22.0), trimmed_mean(&[1.0, 2.0, 3.0, 4.0, 100.0], 0.1));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, trimmed_mean(&[], 0.1));
+/// assert_eq!(None, trimmed_mean(&[1.0], 0.5));
+/// ```
+pub fn trimmed_mean(nums: &[f64], fraction: f64) -> Option<f64> {
+    let k = trim_count(nums.len(), fraction)?;
+    if nums.is_empty() {
+        return None;
+    }
+    if has_nan(nums) {
+        return Some(f64::NAN);
+    }
+    let mut nums = nums.to_owned();
+    accumulate(RunningMean::default(), trim_in_place(&mut nums, k))
+}
+
+/// Winsorized mean of input values: the mean after the
+/// smallest and largest `fraction` of them (rounded down)
+/// are replaced by the nearest value that is kept. A
+/// fraction of 0 gives the mean. The winsorized mean is
+/// undefined for an empty list, or unless `fraction` is at
+/// least 0 and below 0.5.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// // The mean of 2, 2, 3, 4, 4.
+/// assert_eq!(Some(3.0), winsorized_mean(&[1.0, 2.0, 3.0, 4.0, 100.0], 0.2));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, winsorized_mean(&[1.0], -0.1));
+/// ```
+pub fn winsorized_mean(nums: &[f64], fraction: f64) -> Option<f64> {
+    let k = trim_count(nums.len(), fraction)?;
+    if nums.is_empty() {
+        return None;
+    }
+    if has_nan(nums) {
+        return Some(f64::NAN);
+    }
+    let mut nums = nums.to_owned();
+    let kept = trim_in_place(&mut nums, k);
+    let low = kept.iter().copied().fold(f64::INFINITY, f64::min);
+    let high = kept.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let mut acc = RunningMean::default();
+    acc.push_all(&*kept);
+    for _ in 0..k {
+        acc.push(low);
+        acc.push(high);
+    }
+    acc.result()
+}
+
+/// Median absolute deviation of input values: the median
+/// of their absolute deviations from their median. The
+/// median absolute deviation is undefined for an empty list.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(1.0), mad(&[1.0, 2.0, 3.0, 4.0, 100.0]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(0.5), mad(&[1.0, 2.0]));
+/// assert_eq!(None, mad(&[]));
+/// ```
+pub fn mad(nums: &[f64]) -> Option<f64> {
+    let mut nums = nums.to_owned();
+    let center = median_in_place(&mut nums, MedianPolicy::Midpoint)?;
+    for x in &mut nums {
+        *x = (*x - center).abs();
+    }
+    median_in_place(&mut nums, MedianPolicy::Midpoint)
+}
+
+/// Median absolute deviation of input values, scaled to
+/// estimate the standard deviation of normally distributed
+/// values: [`mad`] times 1/Φ⁻¹(3/4) ≈ 1.4826. It is undefined
+/// for an empty list.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(1.482602218505602), normal_mad(&[1.0, 2.0, 3.0, 4.0, 100.0]));
+/// ```
+pub fn normal_mad(nums: &[f64]) -> Option<f64> {
+    mad(nums).map(|mad| mad * MAD_NORMAL_SCALE)
+}
+
+/// Interquartile range of input values: the third quartile
+/// less the first, with the quartiles interpolated by
+/// `method` as in [`quantile`](crate::quantile). The
+/// interquartile range is undefined for an empty list.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(2.0), iqr(&[1.0, 2.0, 3.0, 4.0, 100.0], QuantileMethod::Linear));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(0.0), iqr(&[7.0], QuantileMethod::Linear));
+/// assert_eq!(None, iqr(&[], QuantileMethod::Linear));
+/// ```
+pub fn iqr(nums: &[f64], method: QuantileMethod) -> Option<f64> {
+    let q = crate::quantiles(nums, &[0.25, 0.75], method)?;
+    Some(q[1] - q[0])
+}
+
+/// Hodges–Lehmann estimator of the location of input
+/// values: the median of the averages of all pairs of
+/// values, each value paired with itself included. It is as
+/// efficient as the mean for normal values, but tolerates
+/// nearly 30% wild ones. The averages are held in memory, so
+/// this takes time and space quadratic in the number of
+/// values. The estimator is undefined for an empty list.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(3.0), hodges_lehmann(&[1.0, 2.0, 3.0, 4.0, 100.0]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(1.5), hodges_lehmann(&[1.0, 2.0]));
+/// assert_eq!(None, hodges_lehmann(&[]));
+/// ```
+pub fn hodges_lehmann(nums: &[f64]) -> Option<f64> {
+    let mut averages = Vec::with_capacity(nums.len() * (nums.len() + 1) / 2);
+    for (i, &x) in nums.iter().enumerate() {
+        for &y in &nums[i..] {
+            averages.push(crate::midpoint(x, y));
+        }
+    }
+    median_in_place(&mut averages, MedianPolicy::Midpoint)
+}
+
+/// Huber M-estimator of the location of input values, with
+/// tuning constant `k`. Values within `k` scale units of the
+/// estimate count fully, as for the mean; values further out
+/// are down-weighted so that they count as if they were `k`
+/// units away. The scale is the [`normal_mad`], and the
+/// estimate is found by iteratively reweighted averaging
+/// starting from the median. A `k` of 1.345 gives 95% of the
+/// efficiency of the mean for normal values. If more than
+/// half the values are equal the scale is zero, and the
+/// estimate is the median. The estimator is undefined for
+/// an empty list, or unless `k` is positive.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// let nums = [1.0, 2.0, 3.0, 4.0, 100.0];
+/// let huber = huber_location(&nums, 1.345).unwrap();
+/// assert!((huber - 3.0).abs() < 1e-9);
+/// ```
+/// ```
+/// # use stats::*;
+/// // Very large k treats every value fully.
+/// let nums = [1.0, 2.0, 3.0, 4.0, 100.0];
+/// assert!((huber_location(&nums, 1e9).unwrap() - 22.0).abs() < 1e-9);
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(5.0), huber_location(&[5.0, 5.0, 6.0], 1.345));
+/// assert_eq!(Some(2.0), huber_location(&[1.0, 2.0, 3.0, f64::INFINITY, f64::NEG_INFINITY], 1.345));
+/// assert_eq!(None, huber_location(&[1.0], 0.0));
+/// ```
+pub fn huber_location(nums: &[f64], k: f64) -> Option<f64> {
+    /// Limit on the reweighting iterations.
+    const MAX_ITERATIONS: usize = 100;
+
+    if k.is_nan() || k <= 0.0 {
+        return None;
+    }
+    let mut copy = nums.to_owned();
+    let mut estimate = median_in_place(&mut copy, MedianPolicy::Midpoint)?;
+    let scale = normal_mad(nums)?;
+    if estimate.is_nan() || scale == 0.0 || !scale.is_finite() {
+        return Some(estimate);
+    }
+    let cutoff = k * scale;
+    for _ in 0..MAX_ITERATIONS {
+        // The weighted mean of the values, written as a step
+        // from the current estimate so that infinite values,
+        // whose weight is zero, do not make it NaN.
+        let (mut step, mut weights) = (CompensatedSum::default(), CompensatedSum::default());
+        for &x in nums {
+            let deviation = x - estimate;
+            if deviation.abs() <= cutoff {
+                weights.add(1.0);
+            } else {
+                weights.add(cutoff / deviation.abs());
+            }
+            step.add(deviation.clamp(-cutoff, cutoff));
+        }
+        let next = estimate + step.value() / weights.value();
+        let done = (next - estimate).abs() <= 1e-12 * scale;
+        estimate = next;
+        if done {
+            break;
+        }
+    }
+    Some(estimate)
+}
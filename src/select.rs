@@ -0,0 +1,92 @@
+// Copyright © 2019 Bader Alshaya
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Order statistics by selection: finding the k-th smallest
+//! value in expected linear time, without sorting.
+
+use std::cmp::Ordering;
+
+/// Ordering of input values. Callers must rule out NaN
+/// first.
+pub(crate) fn compare(a: &f64, b: &f64) -> Ordering {
+    // https://users.rust-lang.org/t/how-to-sort-a-vec-of-floats/2838/2
+    a.partial_cmp(b).unwrap()
+}
+
+/// The `k`-th smallest of the input values, counting from
+/// 0, found in place. The values are reordered so that the
+/// result is at index `k`, with no larger value before it
+/// and no smaller value after it. This runs in expected
+/// linear time (the standard library's introselect), against
+/// O(n log n) for a full sort. The `k`-th smallest value is
+/// undefined if there are not more than `k` values, and NaN
+/// if any value is NaN; in the latter case the values are
+/// left in their original order.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// let mut nums = [5.0, 1.0, 4.0, 2.0, 3.0];
+/// assert_eq!(Some(2.0), select_in_place(&mut nums, 1));
+/// assert!(nums[..1].iter().all(|&x| x <= 2.0));
+/// assert!(nums[2..].iter().all(|&x| x >= 2.0));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, select_in_place(&mut [1.0, 2.0], 2));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert!(select_in_place(&mut [1.0, f64::NAN], 0).unwrap().is_nan());
+/// ```
+pub fn select_in_place(nums: &mut [f64], k: usize) -> Option<f64> {
+    if k >= nums.len() {
+        return None;
+    }
+    if nums.iter().any(|x| x.is_nan()) {
+        return Some(f64::NAN);
+    }
+    let (_, kth, _) = nums.select_nth_unstable_by(k, compare);
+    Some(*kth)
+}
+
+/// The `k`-th smallest of the input values, counting from
+/// 0. This is [`select_in_place`] on a copy of the input;
+/// callers that can give up the order of their input should
+/// use that instead and save the copy.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(-1.0), order_statistic(&[0.0, 0.5, -1.0, 1.0], 0));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(1.0), order_statistic(&[0.0, 0.5, -1.0, 1.0], 3));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, order_statistic(&[], 0));
+/// ```
+pub fn order_statistic(nums: &[f64], k: usize) -> Option<f64> {
+    select_in_place(&mut nums.to_owned(), k)
+}
+
+/// Reorder the input values in place so that the `k`
+/// smallest come first and the `k` largest last, and return
+/// the ones in between. Callers must rule out NaN first, and
+/// must leave at least one value in between.
+pub(crate) fn trim_in_place(nums: &mut [f64], k: usize) -> &mut [f64] {
+    if k == 0 {
+        return nums;
+    }
+    nums.select_nth_unstable_by(k - 1, compare);
+    let rest = &mut nums[k..];
+    let m = rest.len() - k;
+    rest.select_nth_unstable_by(m, compare);
+    &mut rest[..m]
+}
@@ -0,0 +1,681 @@
+// Copyright © 2019 Bader Alshaya
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Special functions needed for p-values and probability
+//! distributions: the log gamma function, the error
+//! function, the regularized incomplete gamma and beta
+//! functions and their inverses, Kolmogorov's distribution
+//! and the studentized range. The incomplete functions
+//! follow Press et al., "Numerical Recipes", chapter 6, with
+//! their leading factors computed as in Loader, "Fast and
+//! Accurate Computation of Binomial Probabilities" (2000),
+//! so that large parameters keep their precision.
+
+use std::f64::consts::{PI, SQRT_2};
+
+/// Relative accuracy sought from the series and continued
+/// fractions.
+const EPSILON: f64 = 1e-15;
+
+/// Limit on the terms of a series or continued fraction.
+/// They take a number of terms that grows with the square
+/// root of the parameters.
+const MAX_TERMS: usize = 1_000_000;
+
+/// Limit on the steps of Halley's method in the inverse
+/// functions, which normally converge in a handful.
+const MAX_STEPS: usize = 100;
+
+/// Smallest magnitude allowed for a denominator in the
+/// modified Lentz method, to avoid dividing by zero.
+const TINY: f64 = 1e-300;
+
+/// Natural logarithm of the gamma function, for positive
+/// `x`, by the Lanczos approximation (g = 7, nine terms),
+/// accurate to about 15 digits.
+pub(crate) fn ln_gamma(x: f64) -> f64 {
+    const G: f64 = 7.0;
+    const COEFFICIENTS: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+    if x < 0.5 {
+        // Reflection formula.
+        return (PI / (PI * x).sin()).abs().ln() - ln_gamma(1.0 - x);
+    }
+    let x = x - 1.0;
+    let mut sum = COEFFICIENTS[0];
+    for (i, &c) in COEFFICIENTS.iter().enumerate().skip(1) {
+        sum += c / (x + i as f64);
+    }
+    let t = x + G + 0.5;
+    0.5 * (2.0 * PI).ln() + (x + 0.5) * t.ln() - t + sum.ln()
+}
+
+/// Natural logarithm of the beta function B(a, b), for
+/// positive `a` and `b`. Stirling's formula for each gamma
+/// function lets the large terms cancel exactly, so a large
+/// parameter keeps the precision of the result.
+pub(crate) fn ln_beta(a: f64, b: f64) -> f64 {
+    let (a, b) = if a >= b { (a, b) } else { (b, a) };
+    0.5 * (2.0 * PI).ln() + (b - 0.5) * b.ln() - (a - 0.5) * (b / a).ln_1p() - b * (a + b).ln()
+        + stirling_error(a)
+        + stirling_error(b)
+        - stirling_error(a + b)
+}
+
+/// Error of Stirling's approximation to ln Γ(a): ln Γ(a) less
+/// (a - 1/2) ln a - a + ln √(2π). For large `a` it comes
+/// from its asymptotic series, since the difference would
+/// cancel most of the digits.
+fn stirling_error(a: f64) -> f64 {
+    if a < 10.0 {
+        return ln_gamma(a) - ((a - 0.5) * a.ln() - a + 0.5 * (2.0 * PI).ln());
+    }
+    // Bernoulli numbers B(2k) / (2k (2k - 1)), from the
+    // highest order down.
+    const COEFFICIENTS: [f64; 8] = [
+        -3617.0 / 122_400.0,
+        1.0 / 156.0,
+        -691.0 / 360_360.0,
+        1.0 / 1188.0,
+        -1.0 / 1680.0,
+        1.0 / 1260.0,
+        -1.0 / 360.0,
+        1.0 / 12.0,
+    ];
+    let r = 1.0 / (a * a);
+    COEFFICIENTS.iter().fold(0.0, |sum, c| sum * r + c) / a
+}
+
+/// t - ln(1 + t), for t > -1, without cancellation for small
+/// `t`. The caller also passes `ratio`, equal to 1 + t, which
+/// for t near -1 is more precise than adding 1 to `t`.
+fn log_deviation(t: f64, ratio: f64) -> f64 {
+    if t.abs() >= 0.1 {
+        return t - ratio.ln();
+    }
+    // The series t²/2 - t³/3 + t⁴/4 - …
+    let mut sum = 0.0;
+    let mut power = t;
+    for k in 2..40 {
+        power *= -t;
+        let term = -power / k as f64;
+        sum += term;
+        if term.abs() < sum.abs() * f64::EPSILON {
+            break;
+        }
+    }
+    sum
+}
+
+/// The factor xᵃ e⁻ˣ / Γ(a) that leads the incomplete gamma
+/// functions, for positive `a` and `x`. Written as a power
+/// of x/a and Stirling's formula for Γ(a), the large terms
+/// cancel exactly.
+pub(crate) fn gamma_prefix(a: f64, x: f64) -> f64 {
+    (a / (2.0 * PI)).sqrt() * (-a * log_deviation((x - a) / a, x / a) - stirling_error(a)).exp()
+}
+
+/// The factor xᵃ yᵇ / B(a, b) that leads the incomplete beta
+/// function, where y = 1 - x, for positive `a` and `b` and x
+/// strictly between 0 and 1. As for [`gamma_prefix`], it is
+/// written so that the large terms cancel exactly.
+pub(crate) fn beta_prefix(a: f64, b: f64, x: f64, y: f64) -> f64 {
+    let c = a + b;
+    let deviations = a * log_deviation((x * b - y * a) / a, x * c / a)
+        + b * log_deviation((y * a - x * b) / b, y * c / b);
+    let stirling = stirling_error(c) - stirling_error(a) - stirling_error(b);
+    (a * b / (2.0 * PI * c)).sqrt() * (stirling - deviations).exp()
+}
+
+/// Regularized lower incomplete gamma function P(a, x), for
+/// positive `a` and non-negative `x`.
+pub(crate) fn gamma_p(a: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        0.0
+    } else if x == f64::INFINITY {
+        1.0
+    } else if x < a + 1.0 {
+        gamma_series(a, x)
+    } else {
+        1.0 - gamma_continued_fraction(a, x)
+    }
+}
+
+/// Regularized upper incomplete gamma function Q(a, x), for
+/// positive `a` and non-negative `x`. This is one less
+/// P(a, x), but computed directly so that small upper tails
+/// keep their precision.
+pub(crate) fn gamma_q(a: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        1.0
+    } else if x == f64::INFINITY {
+        0.0
+    } else if x < a + 1.0 {
+        1.0 - gamma_series(a, x)
+    } else {
+        gamma_continued_fraction(a, x)
+    }
+}
+
+/// P(a, x) by its series, which converges quickly for
+/// x < a + 1.
+fn gamma_series(a: f64, x: f64) -> f64 {
+    let mut term = 1.0 / a;
+    let mut sum = term;
+    let mut ap = a;
+    for _ in 0..MAX_TERMS {
+        ap += 1.0;
+        term *= x / ap;
+        sum += term;
+        if term.abs() < sum.abs() * EPSILON {
+            break;
+        }
+    }
+    sum * gamma_prefix(a, x)
+}
+
+/// Q(a, x) by its continued fraction, which converges
+/// quickly for x ≥ a + 1.
+fn gamma_continued_fraction(a: f64, x: f64) -> f64 {
+    let mut b = x + 1.0 - a;
+    let mut c = 1.0 / TINY;
+    let mut d = 1.0 / b;
+    let mut h = d;
+    for i in 1..MAX_TERMS {
+        let an = -(i as f64) * (i as f64 - a);
+        b += 2.0;
+        d = an * d + b;
+        if d.abs() < TINY {
+            d = TINY;
+        }
+        c = b + an / c;
+        if c.abs() < TINY {
+            c = TINY;
+        }
+        d = 1.0 / d;
+        let delta = d * c;
+        h *= delta;
+        if (delta - 1.0).abs() < EPSILON {
+            break;
+        }
+    }
+    gamma_prefix(a, x) * h
+}
+
+/// Regularized incomplete beta function I_x(a, b), for
+/// positive `a` and `b` and `x` between 0 and 1.
+pub(crate) fn beta_inc(a: f64, b: f64, x: f64) -> f64 {
+    beta_inc_pair(a, b, x, 1.0 - x).0
+}
+
+/// I_x(a, b) and its complement 1 - I_x(a, b), each computed
+/// directly so that whichever is small keeps its precision.
+/// The caller gives y = 1 - x as well, since it can often
+/// compute it more precisely than by subtraction.
+pub(crate) fn beta_inc_pair(a: f64, b: f64, x: f64, y: f64) -> (f64, f64) {
+    if x <= 0.0 {
+        return (0.0, 1.0);
+    }
+    if y <= 0.0 {
+        return (1.0, 0.0);
+    }
+    let front = beta_prefix(a, b, x, y);
+    // The continued fraction converges quickly below the
+    // mean of the distribution; above it, use the symmetry
+    // I_x(a, b) = 1 - I_{1-x}(b, a).
+    if x < (a + 1.0) / (a + b + 2.0) {
+        let i = front * beta_continued_fraction(a, b, x) / a;
+        (i, 1.0 - i)
+    } else {
+        let complement = front * beta_continued_fraction(b, a, y) / b;
+        (1.0 - complement, complement)
+    }
+}
+
+/// Continued fraction for the incomplete beta function, by
+/// the modified Lentz method.
+fn beta_continued_fraction(a: f64, b: f64, x: f64) -> f64 {
+    let (qab, qap, qam) = (a + b, a + 1.0, a - 1.0);
+    let mut c = 1.0;
+    let mut d = 1.0 - qab * x / qap;
+    if d.abs() < TINY {
+        d = TINY;
+    }
+    d = 1.0 / d;
+    let mut h = d;
+    for m in 1..MAX_TERMS {
+        let m = m as f64;
+        let m2 = 2.0 * m;
+        // Even step.
+        let aa = m * (b - m) * x / ((qam + m2) * (a + m2));
+        d = 1.0 + aa * d;
+        if d.abs() < TINY {
+            d = TINY;
+        }
+        c = 1.0 + aa / c;
+        if c.abs() < TINY {
+            c = TINY;
+        }
+        d = 1.0 / d;
+        h *= d * c;
+        // Odd step.
+        let aa = -(a + m) * (qab + m) * x / ((a + m2) * (qap + m2));
+        d = 1.0 + aa * d;
+        if d.abs() < TINY {
+            d = TINY;
+        }
+        c = 1.0 + aa / c;
+        if c.abs() < TINY {
+            c = TINY;
+        }
+        d = 1.0 / d;
+        let delta = d * c;
+        h *= delta;
+        if (delta - 1.0).abs() < EPSILON {
+            break;
+        }
+    }
+    h
+}
+
+/// Complementary error function, through its relation to
+/// the upper incomplete gamma function: erfc(x) =
+/// Q(1/2, x²) for x ≥ 0.
+pub(crate) fn erfc(x: f64) -> f64 {
+    if x < 0.0 {
+        2.0 - gamma_q(0.5, x * x)
+    } else {
+        gamma_q(0.5, x * x)
+    }
+}
+
+/// Standard normal distribution function Φ(z).
+pub(crate) fn normal_cdf(z: f64) -> f64 {
+    0.5 * erfc(-z / SQRT_2)
+}
+
+/// Natural logarithm of Φ(z), keeping its precision where
+/// Φ(z) would underflow or round to one. Far in the lower
+/// tail it comes from the asymptotic series ln Φ(z) =
+/// -z²/2 - ln(-z) - ln √(2π) + ln(1 - 1/z² + 3/z⁴ - …).
+pub(crate) fn ln_normal_cdf(z: f64) -> f64 {
+    if z < -20.0 {
+        let z2 = z * z;
+        let (mut term, mut series) = (1.0, 0.0);
+        for k in 1..MAX_TERMS {
+            term *= -(2.0 * k as f64 - 1.0) / z2;
+            series += term;
+            if term.abs() < EPSILON {
+                break;
+            }
+        }
+        -0.5 * z2 - (-z).ln() - 0.5 * (2.0 * PI).ln() + series.ln_1p()
+    } else if z > 0.0 {
+        (-normal_cdf(-z)).ln_1p()
+    } else {
+        normal_cdf(z).ln()
+    }
+}
+
+/// Two-sided p-value of a standard normal statistic `z`.
+pub(crate) fn normal_two_sided(z: f64) -> f64 {
+    erfc(z.abs() / SQRT_2)
+}
+
+/// Two-sided p-value of a Student's t statistic `t` with
+/// `df` degrees of freedom.
+pub(crate) fn t_two_sided(t: f64, df: f64) -> f64 {
+    if t.is_infinite() {
+        return 0.0;
+    }
+    beta_inc(df / 2.0, 0.5, df / (df + t * t))
+}
+
+/// Survival function of Kolmogorov's distribution: the
+/// limiting probability that √n times the Kolmogorov-Smirnov
+/// statistic of `n` values exceeds `x`. The alternating
+/// series converges quickly for large `x`; for small `x` the
+/// distribution function is summed instead, from its Jacobi
+/// theta form.
+pub(crate) fn kolmogorov_sf(x: f64) -> f64 {
+    if x <= 0.0 {
+        return 1.0;
+    }
+    if x < 1.0 {
+        let z = -PI * PI / (8.0 * x * x);
+        let cdf: f64 = (1..20)
+            .step_by(2)
+            .map(|k| ((k * k) as f64 * z).exp())
+            .sum::<f64>()
+            * (2.0 * PI).sqrt()
+            / x;
+        return 1.0 - cdf;
+    }
+    let mut sum = 0.0;
+    let mut sign = 1.0;
+    for k in 1..=100 {
+        let term = (-2.0 * (k * k) as f64 * x * x).exp();
+        sum += sign * term;
+        if term < sum * f64::EPSILON {
+            break;
+        }
+        sign = -sign;
+    }
+    (2.0 * sum).min(1.0)
+}
+
+/// Positive nodes of 16-point Gauss-Legendre quadrature on
+/// [-1, 1], which are symmetric about 0, with their weights.
+const LEGENDRE: [(f64, f64); 8] = [
+    (0.9894009349916499, 0.027152459411754096),
+    (0.9445750230732326, 0.062253523938647894),
+    (0.8656312023878318, 0.09515851168249279),
+    (0.755404408355003, 0.12462897125553388),
+    (0.6178762444026438, 0.14959598881657674),
+    (0.45801677765722737, 0.16915651939500254),
+    (0.2816035507792589, 0.18260341504492358),
+    (0.09501250983763744, 0.1894506104550685),
+];
+
+/// The nodes and weights of 16-point Gauss-Legendre
+/// quadrature on each of `pieces` equal parts of [a, b].
+fn quadrature(a: f64, b: f64, pieces: usize) -> impl Iterator<Item = (f64, f64)> {
+    let half = (b - a) / pieces as f64 / 2.0;
+    (0..pieces).flat_map(move |i| {
+        let mid = a + (2 * i + 1) as f64 * half;
+        LEGENDRE
+            .iter()
+            .flat_map(move |&(x, w)| [(mid - x * half, w * half), (mid + x * half, w * half)])
+    })
+}
+
+/// Survival function of the studentized range: the
+/// probability that the range of `k` standard normal values,
+/// divided by an independent estimate of their standard
+/// deviation with `df` degrees of freedom, exceeds `q`. An
+/// infinite `df` gives the range of the normal values
+/// themselves. The double integral is computed by
+/// Gauss-Legendre quadrature, to an absolute accuracy of
+/// about 1e-10.
+pub(crate) fn studentized_range_sf(q: f64, k: f64, df: f64) -> f64 {
+    if q.is_nan() {
+        return f64::NAN;
+    }
+    if q <= 0.0 {
+        return 1.0;
+    }
+    // The range of the normal values exceeds w unless the
+    // others all fall within w above the smallest, at z. The
+    // density of the smallest, and the normal tails, are
+    // only needed at the nodes over z.
+    let nodes: Vec<(f64, f64, f64, f64)> = quadrature(-8.0, 8.0, 8)
+        .map(|(z, w)| {
+            let density = w * k * (-z * z / 2.0).exp() / (2.0 * PI).sqrt();
+            (z, density, normal_cdf(z), normal_cdf(-z))
+        })
+        .collect();
+    let range_sf = |w: f64| {
+        let cdf: f64 = nodes
+            .iter()
+            .map(|&(z, density, lower, upper)| {
+                let within = if z > 0.0 {
+                    upper - normal_cdf(-z - w)
+                } else {
+                    normal_cdf(z + w) - lower
+                };
+                density * within.powf(k - 1.0)
+            })
+            .sum();
+        (1.0 - cdf).clamp(0.0, 1.0)
+    };
+    if df.is_infinite() {
+        return range_sf(q);
+    }
+    // Average over the estimate s of the standard deviation,
+    // where df s² is chi-squared, between quantiles far
+    // enough out that the rest is negligible.
+    let a = df / 2.0;
+    let low = (inverse_gamma_p(a, 1e-15, 1.0 - 1e-15) / a).sqrt();
+    let high = (inverse_gamma_p(a, 1.0 - 1e-15, 1e-15) / a).sqrt();
+    let ln_norm = std::f64::consts::LN_2 + a * a.ln() - ln_gamma(a);
+    let sf: f64 = quadrature(low, high, 16)
+        .map(|(s, w)| {
+            let density = (ln_norm + (df - 1.0) * s.ln() - a * s * s).exp();
+            w * density * range_sf(q * s)
+        })
+        .sum();
+    sf.clamp(0.0, 1.0)
+}
+
+/// The `q` at which the studentized range survival function
+/// [`studentized_range_sf`] is `alpha`, for `alpha` strictly
+/// between 0 and 1. The root is bracketed and then found by
+/// the Illinois method on the log of the survival function.
+pub(crate) fn studentized_range_quantile(alpha: f64, k: f64, df: f64) -> f64 {
+    let error = |q: f64| studentized_range_sf(q, k, df).ln() - alpha.ln();
+    let (mut low, mut high) = (0.0, 1.0);
+    let (mut e_low, mut e_high) = (f64::INFINITY, error(high));
+    while e_high > 0.0 {
+        low = high;
+        e_low = e_high;
+        high *= 2.0;
+        e_high = error(high);
+    }
+    // Whether the high end was kept by the last step.
+    let mut kept = None;
+    for _ in 0..MAX_STEPS {
+        let q = if e_low.is_finite() {
+            high - e_high * (high - low) / (e_high - e_low)
+        } else {
+            (low + high) / 2.0
+        };
+        let e = error(q);
+        if e.abs() < 1e-14 || (high - low) < 1e-12 * high {
+            return q;
+        }
+        // The end kept twice in a row has its error halved,
+        // so that it is soon replaced too.
+        if (e > 0.0) == (e_low > 0.0) {
+            low = q;
+            e_low = e;
+            if kept == Some(true) {
+                e_high /= 2.0;
+            }
+            kept = Some(true);
+        } else {
+            high = q;
+            e_high = e;
+            if kept == Some(false) {
+                e_low /= 2.0;
+            }
+            kept = Some(false);
+        }
+    }
+    (low + high) / 2.0
+}
+
+/// Inverse of the standard normal distribution function, for
+/// `p` strictly between 0 and 1. Acklam's rational
+/// approximation, good to about nine digits, is refined by
+/// Halley's method. Lower-tail values are solved directly
+/// and upper-tail ones by symmetry, so that `p` near 0 keeps
+/// its precision.
+pub(crate) fn normal_quantile(p: f64) -> f64 {
+    if p > 0.5 {
+        return -normal_quantile(1.0 - p);
+    }
+    const A: [f64; 6] = [
+        -3.969_683_028_665_376e1,
+        2.209_460_984_245_205e2,
+        -2.759_285_104_469_687e2,
+        1.383_577_518_672_69e2,
+        -3.066_479_806_614_716e1,
+        2.506_628_277_459_239,
+    ];
+    const B: [f64; 5] = [
+        -5.447_609_879_822_406e1,
+        1.615_858_368_580_409e2,
+        -1.556_989_798_598_866e2,
+        6.680_131_188_771_972e1,
+        -1.328_068_155_288_572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784_894_002_430_293e-3,
+        -3.223_964_580_411_365e-1,
+        -2.400_758_277_161_838,
+        -2.549_732_539_343_734,
+        4.374_664_141_464_968,
+        2.938_163_982_698_783,
+    ];
+    const D: [f64; 4] = [
+        7.784_695_709_041_462e-3,
+        3.224_671_290_700_398e-1,
+        2.445_134_137_142_996,
+        3.754_408_661_907_416,
+    ];
+    let polynomial = |coefficients: &[f64], x: f64| coefficients.iter().fold(0.0, |y, c| y * x + c);
+    let mut z = if p < 0.02425 {
+        let q = (-2.0 * p.ln()).sqrt();
+        polynomial(&C, q) / (polynomial(&D, q) * q + 1.0)
+    } else {
+        let q = p - 0.5;
+        let r = q * q;
+        polynomial(&A, r) * q / (polynomial(&B, r) * r + 1.0)
+    };
+    for _ in 0..2 {
+        let error = normal_cdf(z) - p;
+        let u = error * (2.0 * PI).sqrt() * (z * z / 2.0).exp();
+        z -= u / (1.0 + z * u / 2.0);
+    }
+    z
+}
+
+/// The `x` at which P(a, x) = `p` and Q(a, x) = `q`, where
+/// `q` is 1 - `p` but given separately so that upper tails
+/// keep their precision. The initial guess and the Halley
+/// steps follow "Numerical Recipes", section 6.2.1, solving
+/// for whichever tail is smaller.
+pub(crate) fn inverse_gamma_p(a: f64, p: f64, q: f64) -> f64 {
+    if p <= 0.0 {
+        return 0.0;
+    }
+    if q <= 0.0 {
+        return f64::INFINITY;
+    }
+    let lower = p <= q;
+    let mut x = if a > 1.0 {
+        // Wilson and Hilferty's cube-root normal approximation.
+        let z = if lower {
+            normal_quantile(p)
+        } else {
+            -normal_quantile(q)
+        };
+        let x = a * (1.0 - 1.0 / (9.0 * a) + z / (3.0 * a.sqrt())).powi(3);
+        x.max(1e-3)
+    } else {
+        // P(a, x) is roughly xᵃ / Γ(a + 1) for small x and
+        // 1 - e⁻ˣ for large.
+        let t = 1.0 - a * (0.253 + a * 0.12);
+        if p < t {
+            (p / t).powf(1.0 / a)
+        } else {
+            1.0 - (q / (1.0 - t)).ln()
+        }
+    };
+    for _ in 0..MAX_STEPS {
+        if x <= 0.0 {
+            return 0.0;
+        }
+        let error = if lower {
+            gamma_p(a, x) - p
+        } else {
+            q - gamma_q(a, x)
+        };
+        let density = gamma_prefix(a, x) / x;
+        if density == 0.0 {
+            break;
+        }
+        let u = error / density;
+        let step = u / (1.0 - 0.5 * (u * ((a - 1.0) / x - 1.0)).min(1.0));
+        x -= step;
+        if x <= 0.0 {
+            x = 0.5 * (x + step);
+        }
+        if step.abs() < EPSILON * x {
+            break;
+        }
+    }
+    x
+}
+
+/// The `x` at which I_x(a, b) = `p` and 1 - I_x(a, b) = `q`,
+/// with `q` given separately as for [`inverse_gamma_p`]. The
+/// initial guess and the Halley steps follow "Numerical
+/// Recipes", section 6.4.
+pub(crate) fn inverse_beta_inc(a: f64, b: f64, p: f64, q: f64) -> f64 {
+    if p <= 0.0 {
+        return 0.0;
+    }
+    if q <= 0.0 {
+        return 1.0;
+    }
+    let lower = p <= q;
+    let mut x = if a >= 1.0 && b >= 1.0 {
+        // The upper normal quantile of p.
+        let z = if lower {
+            -normal_quantile(p)
+        } else {
+            normal_quantile(q)
+        };
+        let l = (z * z - 3.0) / 6.0;
+        let h = 2.0 / (1.0 / (2.0 * a - 1.0) + 1.0 / (2.0 * b - 1.0));
+        let w = z * (l + h).sqrt() / h
+            - (1.0 / (2.0 * b - 1.0) - 1.0 / (2.0 * a - 1.0)) * (l + 5.0 / 6.0 - 2.0 / (3.0 * h));
+        a / (a + b * (2.0 * w).exp())
+    } else {
+        let (ln_a, ln_b) = ((a / (a + b)).ln(), (b / (a + b)).ln());
+        let t = (a * ln_a).exp() / a;
+        let u = (b * ln_b).exp() / b;
+        let w = t + u;
+        if p < t / w {
+            (a * w * p).powf(1.0 / a)
+        } else {
+            1.0 - (b * w * q).powf(1.0 / b)
+        }
+    };
+    for step_number in 0..MAX_STEPS {
+        if x <= 0.0 || x >= 1.0 {
+            return x;
+        }
+        let (i, complement) = beta_inc_pair(a, b, x, 1.0 - x);
+        let error = if lower { i - p } else { q - complement };
+        let density = beta_prefix(a, b, x, 1.0 - x) / (x * (1.0 - x));
+        if density == 0.0 {
+            break;
+        }
+        let u = error / density;
+        let step = u / (1.0 - 0.5 * (u * ((a - 1.0) / x - (b - 1.0) / (1.0 - x))).min(1.0));
+        x -= step;
+        if x <= 0.0 {
+            x = 0.5 * (x + step);
+        }
+        if x >= 1.0 {
+            x = 0.5 * (x + step + 1.0);
+        }
+        if step.abs() < EPSILON * x && step_number > 0 {
+            break;
+        }
+    }
+    x
+}
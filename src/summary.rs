@@ -0,0 +1,119 @@
+// Copyright © 2019 Bader Alshaya
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! A summary of several statistics at once, in the manner of
+//! R's `summary()` or pandas' `describe()`.
+
+use std::fmt;
+
+use crate::quantile::{quantile_sorted, QuantileMethod};
+use crate::select::compare;
+
+/// Summary statistics of a list of values. The quartiles
+/// and median are interpolated linearly (see
+/// [`QuantileMethod::Linear`]), as R and pandas do, so the
+/// median of an even number of values is the average of the
+/// middle two.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Summary {
+    /// Number of values.
+    pub count: usize,
+    /// Smallest value.
+    pub min: f64,
+    /// First quartile.
+    pub q1: f64,
+    /// Median.
+    pub median: f64,
+    /// Third quartile.
+    pub q3: f64,
+    /// Largest value.
+    pub max: f64,
+    /// Arithmetic mean.
+    pub mean: f64,
+    /// Population standard deviation.
+    pub stddev: f64,
+}
+
+impl Summary {
+    /// Names of the fields, in the order of
+    /// [`Summary::values`].
+    pub const LABELS: [&'static str; 8] = [
+        "count", "min", "q1", "median", "q3", "max", "mean", "stddev",
+    ];
+
+    /// The fields as numbers, in the order of
+    /// [`Summary::LABELS`].
+    pub fn values(&self) -> [f64; 8] {
+        [
+            self.count as f64,
+            self.min,
+            self.q1,
+            self.median,
+            self.q3,
+            self.max,
+            self.mean,
+            self.stddev,
+        ]
+    }
+}
+
+/// One field per line, as `name: value`.
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (label, value) in Self::LABELS.iter().zip(self.values().iter()) {
+            writeln!(f, "{}: {}", label, value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Summary statistics of the input values, from a single
+/// sorted copy of them. The summary of an empty list is
+/// undefined; the summary of values including a NaN has NaN
+/// for everything but the count.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// let s = summary(&[4.0, 1.0, 3.0, 2.0]).unwrap();
+/// assert_eq!(4, s.count);
+/// assert_eq!((1.0, 4.0), (s.min, s.max));
+/// assert_eq!((1.75, 2.5, 3.25), (s.q1, s.median, s.q3));
+/// assert_eq!(2.5, s.mean);
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, summary(&[]));
+/// ```
+/// ```
+/// # use stats::*;
+/// let s = summary(&[1.0, f64::NAN]).unwrap();
+/// assert_eq!(2, s.count);
+/// assert!(s.median.is_nan() && s.min.is_nan());
+/// ```
+pub fn summary(nums: &[f64]) -> Option<Summary> {
+    if nums.is_empty() {
+        return None;
+    }
+    let sorted = if nums.iter().any(|x| x.is_nan()) {
+        vec![f64::NAN; nums.len()]
+    } else {
+        let mut sorted = nums.to_owned();
+        sorted.sort_unstable_by(compare);
+        sorted
+    };
+    let q = |p| quantile_sorted(&sorted, p, QuantileMethod::Linear).unwrap();
+    Some(Summary {
+        count: nums.len(),
+        min: sorted[0],
+        q1: q(0.25),
+        median: q(0.5),
+        q3: q(0.75),
+        max: sorted[nums.len() - 1],
+        mean: crate::mean(nums)?,
+        stddev: crate::stddev(nums)?,
+    })
+}
@@ -0,0 +1,252 @@
+// Copyright © 2019 Bader Alshaya
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Student's t-tests of a mean, of paired differences, and
+//! of the difference between the means of two independent
+//! samples. A NaN value makes the whole result NaN.
+
+use crate::distributions::{Distribution, StudentsT};
+use crate::{mean, sample_variance, StatsError};
+
+/// The result of a t-test: the t statistic of an estimated
+/// difference, with its p-values and confidence intervals.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TTest {
+    /// The t statistic: the estimate divided by its standard
+    /// error.
+    pub statistic: f64,
+    /// Degrees of freedom of the t distribution of the
+    /// statistic. They need not be whole.
+    pub degrees_of_freedom: f64,
+    /// The estimated difference that is tested against
+    /// zero.
+    pub estimate: f64,
+    /// Standard error of the estimate.
+    pub standard_error: f64,
+    /// Two-sided p-value: the probability of a statistic at
+    /// least this far from zero if the true difference were
+    /// zero.
+    pub p_value: f64,
+    /// One-sided p-value of the alternative that the true
+    /// difference is less than zero.
+    pub p_less: f64,
+    /// One-sided p-value of the alternative that the true
+    /// difference is greater than zero.
+    pub p_greater: f64,
+}
+
+impl TTest {
+    /// Result for input containing NaN.
+    const NAN: TTest = TTest {
+        statistic: f64::NAN,
+        degrees_of_freedom: f64::NAN,
+        estimate: f64::NAN,
+        standard_error: f64::NAN,
+        p_value: f64::NAN,
+        p_less: f64::NAN,
+        p_greater: f64::NAN,
+    };
+
+    /// The test of `estimate`, with the given standard error
+    /// and degrees of freedom, against zero. A NaN statistic
+    /// has NaN p-values.
+    fn new(
+        estimate: f64,
+        standard_error: f64,
+        degrees_of_freedom: f64,
+    ) -> Result<TTest, StatsError> {
+        let statistic = estimate / standard_error;
+        let t = StudentsT::new(degrees_of_freedom)?;
+        let (p_less, p_greater) = (t.cdf(statistic), t.sf(statistic));
+        let p_value = if statistic.is_nan() {
+            f64::NAN
+        } else {
+            (2.0 * p_less.min(p_greater)).min(1.0)
+        };
+        Ok(TTest {
+            statistic,
+            degrees_of_freedom,
+            estimate,
+            standard_error,
+            p_value,
+            p_less,
+            p_greater,
+        })
+    }
+
+    /// Two-sided confidence interval for the true
+    /// difference, at the given confidence `level` (0.95 for
+    /// a 95% interval), as its lower and upper ends. The ends
+    /// are NaN unless `level` is between 0 and 1.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use stats::*;
+    /// let test = one_sample_t_test(&[4.0, 6.0, 5.0, 7.0, 3.0], 0.0).unwrap();
+    /// let (lower, upper) = test.confidence_interval(0.95);
+    /// assert!((lower - 3.036756838522439).abs() < 1e-12);
+    /// assert!((upper - 6.963243161477561).abs() < 1e-12);
+    /// ```
+    pub fn confidence_interval(&self, level: f64) -> (f64, f64) {
+        let t = match StudentsT::new(self.degrees_of_freedom) {
+            Ok(t) => t,
+            Err(_) => return (f64::NAN, f64::NAN),
+        };
+        let margin = t.inverse_sf((1.0 - level) / 2.0) * self.standard_error;
+        (self.estimate - margin, self.estimate + margin)
+    }
+}
+
+/// One-sample t-test of the hypothesis that `nums` come from
+/// a distribution of mean `mu`. The estimate is the mean of
+/// `nums` less `mu`, so that adding `mu` to its confidence
+/// interval gives one for the mean. The test is undefined
+/// for fewer than two values, if they are all equal, or if
+/// `mu` is not finite.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// // Extra hours of sleep from a soporific drug, from
+/// // Student's 1908 paper.
+/// let extra = [0.7, -1.6, -0.2, -1.2, -0.1, 3.4, 3.7, 0.8, 0.0, 2.0];
+/// let test = one_sample_t_test(&extra, 0.0).unwrap();
+/// assert!((test.statistic - 1.325710).abs() < 1e-6);
+/// assert_eq!(9.0, test.degrees_of_freedom);
+/// assert!((test.p_value - 0.2175978).abs() < 1e-7);
+/// assert!((test.p_greater - test.p_value / 2.0).abs() < 1e-15);
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, one_sample_t_test(&[1.0], 0.0));
+/// assert_eq!(None, one_sample_t_test(&[2.0, 2.0], 0.0));
+/// // An infinite value leaves the statistic undefined.
+/// let test = one_sample_t_test(&[f64::INFINITY, 1.0, 2.0], 0.0).unwrap();
+/// assert!(test.statistic.is_nan() && test.p_value.is_nan());
+/// ```
+pub fn one_sample_t_test(nums: &[f64], mu: f64) -> Option<TTest> {
+    try_one_sample_t_test(nums, mu).ok()
+}
+
+/// One-sample t-test, or why it is undefined.
+pub(crate) fn try_one_sample_t_test(nums: &[f64], mu: f64) -> Result<TTest, StatsError> {
+    if !mu.is_finite() {
+        return Err(StatsError::InvalidParameter(
+            "hypothesized mean must be finite",
+        ));
+    }
+    StatsError::check_len(nums.len(), 2)?;
+    if nums.iter().any(|x| x.is_nan()) {
+        return Ok(TTest::NAN);
+    }
+    let n = nums.len() as f64;
+    let variance = sample_variance(nums).unwrap();
+    if variance == 0.0 {
+        return Err(StatsError::ZeroVariance);
+    }
+    TTest::new(mean(nums).unwrap() - mu, (variance / n).sqrt(), n - 1.0)
+}
+
+/// Paired t-test of the hypothesis that the differences
+/// `xs[i] - ys[i]` have mean zero, as for measurements of
+/// the same subjects before and after a treatment. The
+/// estimate is the mean difference. The test is undefined
+/// unless there are at least two pairs, or if the
+/// differences are all equal.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// // The sleep data, with both drugs given to each patient.
+/// let drug1 = [0.7, -1.6, -0.2, -1.2, -0.1, 3.4, 3.7, 0.8, 0.0, 2.0];
+/// let drug2 = [1.9, 0.8, 1.1, 0.1, -0.1, 4.4, 5.5, 1.6, 4.6, 3.4];
+/// let test = paired_t_test(&drug1, &drug2).unwrap();
+/// assert!((test.statistic - -4.062128).abs() < 1e-6);
+/// assert!((test.p_value - 0.002832890).abs() < 1e-9);
+/// let (lower, upper) = test.confidence_interval(0.95);
+/// assert!((lower - -2.4598858).abs() < 1e-7);
+/// assert!((upper - -0.7001142).abs() < 1e-7);
+/// ```
+pub fn paired_t_test(xs: &[f64], ys: &[f64]) -> Option<TTest> {
+    try_paired_t_test(xs, ys).ok()
+}
+
+/// Paired t-test, or why it is undefined.
+pub(crate) fn try_paired_t_test(xs: &[f64], ys: &[f64]) -> Result<TTest, StatsError> {
+    if xs.len() != ys.len() {
+        return Err(StatsError::LengthMismatch);
+    }
+    let differences: Vec<f64> = xs.iter().zip(ys).map(|(x, y)| x - y).collect();
+    try_one_sample_t_test(&differences, 0.0)
+}
+
+/// Welch's t-test of the hypothesis that two independent
+/// samples come from distributions of equal means, without
+/// assuming that their variances are equal. The estimate is
+/// the difference of the means, `xs` less `ys`, and the
+/// degrees of freedom are from the Welch–Satterthwaite
+/// equation. The test is undefined unless each sample has at
+/// least two values, or if both have all values equal.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// // The sleep data, taken as two independent groups.
+/// let group1 = [0.7, -1.6, -0.2, -1.2, -0.1, 3.4, 3.7, 0.8, 0.0, 2.0];
+/// let group2 = [1.9, 0.8, 1.1, 0.1, -0.1, 4.4, 5.5, 1.6, 4.6, 3.4];
+/// let test = welch_t_test(&group1, &group2).unwrap();
+/// assert!((test.statistic - -1.860813).abs() < 1e-6);
+/// assert!((test.degrees_of_freedom - 17.77647).abs() < 1e-5);
+/// assert!((test.p_value - 0.07939414).abs() < 1e-8);
+/// let (lower, upper) = test.confidence_interval(0.95);
+/// assert!((lower - -3.3654832).abs() < 1e-7);
+/// assert!((upper - 0.2054832).abs() < 1e-7);
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, welch_t_test(&[1.0, 2.0], &[3.0]));
+/// // Infinite or overflowing values give a NaN result.
+/// let test = welch_t_test(&[f64::INFINITY, 1.0], &[1.0, 2.0]).unwrap();
+/// assert!(test.statistic.is_nan() && test.p_value.is_nan());
+/// let test = welch_t_test(&[1e300, -1e300], &[1.0, 2.0]).unwrap();
+/// assert!(test.degrees_of_freedom.is_nan() && test.p_value.is_nan());
+/// ```
+pub fn welch_t_test(xs: &[f64], ys: &[f64]) -> Option<TTest> {
+    try_welch_t_test(xs, ys).ok()
+}
+
+/// Welch's t-test, or why it is undefined.
+pub(crate) fn try_welch_t_test(xs: &[f64], ys: &[f64]) -> Result<TTest, StatsError> {
+    StatsError::check_len(xs.len(), 2)?;
+    StatsError::check_len(ys.len(), 2)?;
+    if xs.iter().chain(ys).any(|x| x.is_nan()) {
+        return Ok(TTest::NAN);
+    }
+    let (nx, ny) = (xs.len() as f64, ys.len() as f64);
+    // The squared standard errors of the two means.
+    let vx = sample_variance(xs).unwrap() / nx;
+    let vy = sample_variance(ys).unwrap() / ny;
+    if vx == 0.0 && vy == 0.0 {
+        return Err(StatsError::ZeroVariance);
+    }
+    // An infinite value, or an overflowing variance, leaves
+    // the degrees of freedom undefined.
+    if !(vx + vy).is_finite() {
+        return Ok(TTest::NAN);
+    }
+    // Each sample's share of the total, so that tiny
+    // variances cannot underflow when squared.
+    let (fx, fy) = (vx / (vx + vy), vy / (vx + vy));
+    let degrees_of_freedom = 1.0 / (fx * fx / (nx - 1.0) + fy * fy / (ny - 1.0));
+    TTest::new(
+        mean(xs).unwrap() - mean(ys).unwrap(),
+        (vx + vy).sqrt(),
+        degrees_of_freedom,
+    )
+}
@@ -0,0 +1,309 @@
+// Copyright © 2019 Bader Alshaya
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Statistics of weighted values, such as pre-aggregated
+//! values with their counts. Each function takes the values
+//! and their weights as two slices of equal length. Weights
+//! must not be negative, and a value of weight zero is
+//! ignored. The statistics are undefined if the lengths
+//! differ, if any weight is negative or if the weights are
+//! all zero. NaN values or weights propagate into the
+//! result.
+
+use crate::accumulator::CompensatedSum;
+use crate::select::compare;
+use crate::StatsError;
+
+/// What the weights of values mean, which decides how the
+/// sample variance corrects for bias.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WeightKind {
+    /// Each weight counts how many times its value occurred,
+    /// so the weights add up to the sample size.
+    Frequency,
+    /// Each weight says how reliable its value is, for
+    /// example the reciprocal of its variance; only the
+    /// ratios of the weights matter.
+    Reliability,
+}
+
+/// Total of the weights, or why the weighted statistics are
+/// undefined.
+fn total_weight(nums: &[f64], weights: &[f64]) -> Result<f64, StatsError> {
+    if nums.len() != weights.len() {
+        return Err(StatsError::LengthMismatch);
+    }
+    StatsError::check_len(nums.len(), 1)?;
+    if weights.iter().any(|&w| w < 0.0) {
+        return Err(StatsError::NegativeValue);
+    }
+    let mut total = CompensatedSum::default();
+    for &w in weights {
+        total.add(w);
+    }
+    if total.value() == 0.0 {
+        return Err(StatsError::InvalidParameter("weights must not all be zero"));
+    }
+    Ok(total.value())
+}
+
+/// The values with positive (or NaN) weights, paired with
+/// their weights.
+fn weighted<'a>(nums: &'a [f64], weights: &'a [f64]) -> impl Iterator<Item = (f64, f64)> + 'a {
+    nums.iter()
+        .copied()
+        .zip(weights.iter().copied())
+        .filter(|&(_, w)| w != 0.0)
+}
+
+/// Weighted mean and total weight.
+fn mean_and_total(nums: &[f64], weights: &[f64]) -> Result<(f64, f64), StatsError> {
+    let total = total_weight(nums, weights)?;
+    let mut sum = CompensatedSum::default();
+    for (x, w) in weighted(nums, weights) {
+        sum.add(w * x);
+    }
+    Ok((sum.value() / total, total))
+}
+
+/// Weighted sum of squared deviations from the weighted
+/// mean, and the total weight.
+fn sum_squares_and_total(nums: &[f64], weights: &[f64]) -> Result<(f64, f64), StatsError> {
+    let (mean, total) = mean_and_total(nums, weights)?;
+    let mut sum = CompensatedSum::default();
+    for (x, w) in weighted(nums, weights) {
+        sum.add(w * (x - mean) * (x - mean));
+    }
+    Ok((sum.value(), total))
+}
+
+/// Weighted mean of input values: the sum of each value
+/// times its weight, divided by the total weight.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(2.5), weighted_mean(&[1.0, 3.0], &[1.0, 3.0]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, weighted_mean(&[1.0, 3.0], &[0.0, 0.0]));
+/// assert_eq!(None, weighted_mean(&[1.0, 3.0], &[1.0, -1.0]));
+/// assert_eq!(None, weighted_mean(&[1.0], &[]));
+/// ```
+pub fn weighted_mean(nums: &[f64], weights: &[f64]) -> Option<f64> {
+    try_weighted_mean(nums, weights).ok()
+}
+
+/// Weighted mean, or why it is undefined.
+pub(crate) fn try_weighted_mean(nums: &[f64], weights: &[f64]) -> Result<f64, StatsError> {
+    mean_and_total(nums, weights).map(|(mean, _)| mean)
+}
+
+/// Weighted population variance of input values: the
+/// weighted mean of the squared deviations from the weighted
+/// mean. Computed in two passes, the first finding the
+/// mean.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// // The variance of 1, 3, 3, 3.
+/// assert_eq!(Some(0.75), weighted_variance(&[1.0, 3.0], &[1.0, 3.0]));
+/// ```
+pub fn weighted_variance(nums: &[f64], weights: &[f64]) -> Option<f64> {
+    try_weighted_variance(nums, weights).ok()
+}
+
+/// Weighted population variance, or why it is undefined.
+pub(crate) fn try_weighted_variance(nums: &[f64], weights: &[f64]) -> Result<f64, StatsError> {
+    let (sum_squares, total) = sum_squares_and_total(nums, weights)?;
+    Ok(sum_squares / total)
+}
+
+/// Weighted sample variance of input values, corrected for
+/// bias according to what the weights mean. With
+/// [`WeightKind::Frequency`] weights the weighted sum of
+/// squared deviations is divided by the total weight less
+/// one, which agrees with [`sample_variance`](crate::sample_variance)
+/// of the values repeated by their counts; the total weight
+/// must exceed one. With [`WeightKind::Reliability`] weights
+/// it is divided by `V1 - V2/V1`, where `V1` and `V2` are the
+/// sums of the weights and of their squares; there must be
+/// at least two values of positive weight.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// // The sample variance of 1, 3, 3, 3.
+/// assert_eq!(Some(1.0), weighted_sample_variance(&[1.0, 3.0], &[1.0, 3.0], WeightKind::Frequency));
+/// ```
+/// ```
+/// # use stats::*;
+/// // Equal reliability weights give the ordinary sample variance.
+/// let nums = [1.0, 2.0, 4.0];
+/// let var = weighted_sample_variance(&nums, &[0.5; 3], WeightKind::Reliability).unwrap();
+/// assert!((var - sample_variance(&nums).unwrap()).abs() < 1e-12);
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, weighted_sample_variance(&[1.0], &[0.5], WeightKind::Frequency));
+/// assert_eq!(None, weighted_sample_variance(&[1.0, 2.0], &[0.5, 0.25], WeightKind::Frequency));
+/// assert_eq!(None, weighted_sample_variance(&[1.0], &[5.0], WeightKind::Reliability));
+/// ```
+pub fn weighted_sample_variance(nums: &[f64], weights: &[f64], kind: WeightKind) -> Option<f64> {
+    try_weighted_sample_variance(nums, weights, kind).ok()
+}
+
+/// Weighted sample variance, or why it is undefined.
+pub(crate) fn try_weighted_sample_variance(
+    nums: &[f64],
+    weights: &[f64],
+    kind: WeightKind,
+) -> Result<f64, StatsError> {
+    let (sum_squares, total) = sum_squares_and_total(nums, weights)?;
+    let denominator = match kind {
+        WeightKind::Frequency => {
+            if total <= 1.0 {
+                StatsError::check_len(nums.len(), 2)?;
+                return Err(StatsError::InvalidParameter(
+                    "frequency weights must total more than 1",
+                ));
+            }
+            total - 1.0
+        }
+        WeightKind::Reliability => {
+            let positive = weighted(nums, weights).count();
+            StatsError::check_len(positive, 2)?;
+            let mut squares = CompensatedSum::default();
+            for (_, w) in weighted(nums, weights) {
+                squares.add(w * w);
+            }
+            total - squares.value() / total
+        }
+    };
+    Ok(sum_squares / denominator)
+}
+
+/// Weighted median of input values; see
+/// [`weighted_quantile`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(3.0), weighted_median(&[1.0, 3.0, 10.0], &[1.0, 3.0, 1.0]));
+/// ```
+/// ```
+/// # use stats::*;
+/// // Equal weights give the conventional median.
+/// assert_eq!(Some(2.5), weighted_median(&[4.0, 1.0, 3.0, 2.0], &[1.0; 4]));
+/// ```
+pub fn weighted_median(nums: &[f64], weights: &[f64]) -> Option<f64> {
+    weighted_quantile(nums, weights, 0.5)
+}
+
+/// Weighted `p` quantile of input values: the smallest value
+/// at which the weights of it and all smaller values add up
+/// to at least `p` of the total weight. Where they add up to
+/// exactly that, the value is averaged with the next larger
+/// one. This is the weighted form of
+/// [`QuantileMethod::AveragedInvertedCdf`](crate::QuantileMethod::AveragedInvertedCdf):
+/// with whole-number weights it agrees with the quantile of
+/// the values repeated by their counts. The quantile is also
+/// undefined unless `p` is between 0 and 1.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// let nums = [10.0, 20.0, 30.0];
+/// let weights = [1.0, 2.0, 1.0];
+/// assert_eq!(Some(10.0), weighted_quantile(&nums, &weights, 0.0));
+/// assert_eq!(Some(15.0), weighted_quantile(&nums, &weights, 0.25));
+/// assert_eq!(Some(20.0), weighted_quantile(&nums, &weights, 0.5));
+/// assert_eq!(Some(30.0), weighted_quantile(&nums, &weights, 1.0));
+/// ```
+/// ```
+/// # use stats::*;
+/// // Only the proportions of the weights matter.
+/// let nums = [1.0, 2.0, 3.0, 4.0];
+/// assert_eq!(Some(2.5), weighted_quantile(&nums, &[1.0, 2.0, 3.0, 4.0], 0.3));
+/// assert_eq!(Some(2.5), weighted_quantile(&nums, &[0.1, 0.2, 0.3, 0.4], 0.3));
+/// ```
+/// ```
+/// # use stats::*;
+/// // A tiny weight still decides ties.
+/// let nums = [1.0, 2.0, 3.0];
+/// assert_eq!(Some(2.5), weighted_quantile(&nums, &[1e8, 1.0, 1e8 + 1.0], 0.5));
+/// assert_eq!(Some(2.0), weighted_quantile(&nums, &[1e8, 1.0, 1e8], 0.5));
+/// // A NaN value of weight zero is ignored.
+/// assert_eq!(Some(2.5), weighted_median(&[f64::NAN, 2.0, 3.0], &[0.0, 1.0, 1.0]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, weighted_quantile(&[1.0], &[1.0], 1.5));
+/// ```
+pub fn weighted_quantile(nums: &[f64], weights: &[f64], p: f64) -> Option<f64> {
+    weighted_quantiles(nums, weights, &[p]).map(|qs| qs[0])
+}
+
+/// Several weighted quantiles of input values, as for
+/// [`weighted_quantile`], sorting the values only once.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// let qs = weighted_quantiles(&[3.0, 1.0, 2.0], &[1.0, 1.0, 2.0], &[0.25, 0.75]);
+/// assert_eq!(Some(vec![1.5, 2.5]), qs);
+/// ```
+pub fn weighted_quantiles(nums: &[f64], weights: &[f64], ps: &[f64]) -> Option<Vec<f64>> {
+    try_weighted_quantiles(nums, weights, ps).ok()
+}
+
+/// Several weighted quantiles, or why they are undefined.
+pub(crate) fn try_weighted_quantiles(
+    nums: &[f64],
+    weights: &[f64],
+    ps: &[f64],
+) -> Result<Vec<f64>, StatsError> {
+    if !ps.iter().all(|p| (0.0..=1.0).contains(p)) {
+        return Err(StatsError::InvalidParameter(
+            "probability must be between 0 and 1",
+        ));
+    }
+    let total = total_weight(nums, weights)?;
+    if total.is_nan() || weighted(nums, weights).any(|(x, _)| x.is_nan()) {
+        return Ok(vec![f64::NAN; ps.len()]);
+    }
+    let mut sorted: Vec<(f64, f64)> = weighted(nums, weights).collect();
+    sorted.sort_unstable_by(|a, b| compare(&a.0, &b.0));
+    // A cumulative weight within a few ulps of the total
+    // weight of the target counts as reaching it exactly, so
+    // that rounding cannot make the result depend on the
+    // scale of the weights.
+    let tolerance = 4.0 * f64::EPSILON * total;
+    let quantile = |p: f64| {
+        let target = p * total;
+        let mut cumulative = CompensatedSum::default();
+        for (i, &(x, w)) in sorted.iter().enumerate() {
+            cumulative.add(w);
+            let distance = cumulative.value() - target;
+            if distance.abs() <= tolerance && i + 1 < sorted.len() {
+                return crate::midpoint(x, sorted[i + 1].0);
+            }
+            if distance >= -tolerance {
+                return x;
+            }
+        }
+        // Rounding left the cumulative weight short.
+        sorted[sorted.len() - 1].0
+    };
+    Ok(ps.iter().map(|&p| quantile(p)).collect())
+}
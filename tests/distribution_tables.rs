@@ -0,0 +1,115 @@
+// Copyright © 2019 Bader Alshaya
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Accuracy tests of the probability distributions against
+//! published tables: Abramowitz and Stegun, "Handbook of
+//! Mathematical Functions", chapter 26, for the normal, t,
+//! chi-squared and F distributions, and the standard
+//! cumulative tables of the Poisson and binomial
+//! distributions. Each computed value must round to the
+//! tabulated one at the precision the table prints.
+
+use stats::distributions::*;
+
+/// Check that `x` rounds to the tabulated value `table`,
+/// which is printed to `decimals` decimal places.
+fn check(x: f64, table: f64, decimals: i32) {
+    let tolerance = 0.5 * 10f64.powi(-decimals);
+    assert!(
+        (x - table).abs() <= tolerance,
+        "{} differs from the table value {}",
+        x,
+        table
+    );
+}
+
+/// A&S Table 26.1: the normal probability function.
+#[test]
+fn normal_cdf() {
+    let n = Normal::standard();
+    check(n.cdf(1.0), 0.841344746068543, 15);
+    check(n.cdf(2.0), 0.977249868051821, 15);
+    check(n.cdf(3.0), 0.998650101968370, 15);
+    check(n.cdf(-1.0), 1.0 - 0.841344746068543, 15);
+}
+
+/// A&S Table 26.2: the upper tail of the normal
+/// distribution, to ten significant digits.
+#[test]
+fn normal_upper_tail() {
+    let n = Normal::standard();
+    check(n.sf(5.0) * 1e7, 2.866515719, 9);
+}
+
+/// A&S Table 26.5: percentage points of the normal
+/// distribution.
+#[test]
+fn normal_quantiles() {
+    let n = Normal::standard();
+    check(n.inverse_cdf(0.975), 1.959963985, 9);
+    check(n.inverse_cdf(0.995), 2.575829304, 9);
+    check(n.inverse_sf(0.0005), 3.290526731, 9);
+    check(n.inverse_cdf(0.025), -1.959963985, 9);
+}
+
+/// A&S Table 26.10: percentage points of the t distribution.
+#[test]
+fn t_quantiles() {
+    for &(df, t) in &[
+        (1.0, 12.706),
+        (2.0, 4.303),
+        (5.0, 2.571),
+        (10.0, 2.228),
+        (30.0, 2.042),
+    ] {
+        let d = StudentsT::new(df).unwrap();
+        check(d.inverse_sf(0.025), t, 3);
+        check(d.inverse_cdf(0.025), -t, 3);
+    }
+    check(StudentsT::new(10.0).unwrap().inverse_cdf(0.995), 3.169, 3);
+}
+
+/// A&S Table 26.8: percentage points of the chi-squared
+/// distribution.
+#[test]
+fn chi_squared_quantiles() {
+    for &(df, x) in &[
+        (1.0, 3.84146),
+        (2.0, 5.99146),
+        (10.0, 18.3070),
+        (30.0, 43.7730),
+    ] {
+        let d = ChiSquared::new(df).unwrap();
+        check(d.inverse_sf(0.05), x, 3);
+        check(d.sf(x), 0.05, 5);
+    }
+    check(ChiSquared::new(5.0).unwrap().inverse_cdf(0.99), 15.0863, 4);
+    check(ChiSquared::new(10.0).unwrap().inverse_cdf(0.05), 3.94030, 5);
+}
+
+/// A&S Table 26.9: percentage points of the F distribution.
+#[test]
+fn f_quantiles() {
+    check(F::new(1.0, 1.0).unwrap().inverse_sf(0.05), 161.45, 2);
+    check(F::new(5.0, 10.0).unwrap().inverse_sf(0.05), 3.33, 2);
+    check(F::new(10.0, 20.0).unwrap().inverse_sf(0.05), 2.35, 2);
+    check(F::new(2.0, 10.0).unwrap().inverse_sf(0.01), 7.56, 2);
+}
+
+/// Cumulative Poisson probabilities.
+#[test]
+fn poisson() {
+    let d = Poisson::new(2.0).unwrap();
+    check(d.pmf(0), 0.1353, 4);
+    check(d.cdf(3.0), 0.8571, 4);
+    check(Poisson::new(5.0).unwrap().cdf(5.0), 0.6160, 4);
+}
+
+/// Cumulative binomial probabilities.
+#[test]
+fn binomial() {
+    check(Binomial::new(10, 0.5).unwrap().cdf(3.0), 0.1719, 4);
+    check(Binomial::new(20, 0.3).unwrap().cdf(6.0), 0.6080, 4);
+}
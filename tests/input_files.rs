@@ -0,0 +1,74 @@
+// Copyright © 2019 Bader Alshaya
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Tests of the command-line program reading one input file
+//! per column: the files must give the same results as the
+//! columns of a single file would.
+
+use std::path::PathBuf;
+use std::process::Command;
+
+/// A scratch file, removed when dropped.
+struct Scratch(PathBuf);
+
+impl Drop for Scratch {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+/// Write `contents` to a scratch file named `name`.
+fn scratch(name: &str, contents: &str) -> Scratch {
+    let path = std::env::temp_dir().join(format!("stats-{}-{}", std::process::id(), name));
+    std::fs::write(&path, contents).unwrap();
+    Scratch(path)
+}
+
+/// Run the program with `args`, returning its exit status
+/// and standard output.
+fn run(args: &[&str], files: &[&Scratch]) -> (i32, String) {
+    let output = Command::new(env!("CARGO_BIN_EXE_stats"))
+        .args(args)
+        .args(files.iter().map(|file| &file.0))
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    (output.status.code().unwrap(), stdout)
+}
+
+/// Skipping a NaN in one file drops its partner in the
+/// other, so the remaining values stay paired.
+#[test]
+fn paired_files_skip_whole_rows() {
+    let a = scratch("paired-a", "1\nnan\n3\n4\n5\n");
+    let b = scratch("paired-b", "2\n2\n5\nnan\n9\n");
+    let both = scratch("paired-both", "1 2\nnan 2\n3 5\n4 nan\n5 9\n");
+    for flag in ["--paired-t-test", "--wilcoxon", "--pearson"] {
+        let files = run(&["--nan=skip", flag], &[&a, &b]);
+        let columns = run(&["--nan=skip", flag], &[&both]);
+        assert_eq!(0, files.0, "{}", flag);
+        assert_eq!(columns, files, "{}", flag);
+    }
+}
+
+/// Paired files must be the same length.
+#[test]
+fn paired_files_of_different_lengths() {
+    let a = scratch("short-a", "1\n2\n3\n");
+    let b = scratch("short-b", "1\n2\n");
+    assert_eq!(3, run(&["--paired-t-test"], &[&a, &b]).0);
+}
+
+/// Independent samples are filtered each on its own, and may
+/// differ in length.
+#[test]
+fn independent_files_skip_values() {
+    let a = scratch("welch-a", "1\nnan\n3\n4\n5\n");
+    let b = scratch("welch-b", "2\n5\n9\n");
+    let clean = scratch("welch-clean", "1\n3\n4\n5\n");
+    let skipped = run(&["--nan=skip", "--welch-t-test"], &[&a, &b]);
+    assert_eq!(0, skipped.0);
+    assert_eq!(run(&["--welch-t-test"], &[&clean, &b]), skipped);
+}
@@ -0,0 +1,145 @@
+// Copyright © 2019 Bader Alshaya
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Accuracy tests against the NIST Statistical Reference
+//! Datasets (StRD) for univariate summary statistics and
+//! linear regression. The `NumAcc` sets are the ones NIST
+//! rates as hard: a small spread riding on a large offset.
+//! The Longley set is a regression on six highly collinear
+//! predictors.
+//!
+//! NIST certifies the mean and the sample standard
+//! deviation; the population value is derived from the
+//! latter. Accuracy is measured as NIST does, by the log
+//! relative error (the number of correct significant
+//! digits).
+
+/// Parse a fixture file of one number per line.
+fn load(data: &str) -> Vec<f64> {
+    data.lines().map(|s| s.parse().unwrap()).collect()
+}
+
+/// Number of correct significant digits in `x` relative to
+/// the certified value `c`.
+fn lre(x: f64, c: f64) -> f64 {
+    if x == c {
+        return 15.0;
+    }
+    -((x - c).abs() / c.abs()).log10()
+}
+
+/// Check `mean`, `sample_stddev` and `stddev` on `nums`
+/// against the certified mean and sample standard deviation,
+/// requiring at least the given number of correct digits in
+/// each.
+fn check(nums: &[f64], mean: f64, sample_sd: f64, mean_digits: f64, sd_digits: f64) {
+    let n = nums.len() as f64;
+    let sd = sample_sd * ((n - 1.0) / n).sqrt();
+    let got_mean = stats::mean(nums).unwrap();
+    let got_sample_sd = stats::sample_stddev(nums).unwrap();
+    let got_sd = stats::stddev(nums).unwrap();
+    assert!(
+        lre(got_mean, mean) >= mean_digits,
+        "mean {} vs certified {}",
+        got_mean,
+        mean
+    );
+    assert!(
+        lre(got_sample_sd, sample_sd) >= sd_digits,
+        "sample stddev {} vs certified {}",
+        got_sample_sd,
+        sample_sd
+    );
+    assert!(
+        lre(got_sd, sd) >= sd_digits,
+        "stddev {} vs certified {}",
+        got_sd,
+        sd
+    );
+}
+
+#[test]
+fn numacc1() {
+    let nums = load(include_str!("data/NumAcc1.dat"));
+    check(&nums, 10000002.0, 1.0, 15.0, 15.0);
+}
+
+#[test]
+fn numacc2() {
+    let nums = load(include_str!("data/NumAcc2.dat"));
+    check(&nums, 1.2, 0.1, 14.0, 13.0);
+}
+
+// The inputs themselves are not exactly representable, so
+// the deviations of 0.1 carry representation error of
+// roughly one unit in the last place of the offset. That
+// limits the attainable digits in the standard deviation.
+
+#[test]
+fn numacc3() {
+    let nums = load(include_str!("data/NumAcc3.dat"));
+    check(&nums, 1000000.2, 0.1, 14.0, 9.0);
+}
+
+#[test]
+fn numacc4() {
+    let nums = load(include_str!("data/NumAcc4.dat"));
+    check(&nums, 10000000.2, 0.1, 14.0, 8.0);
+}
+
+#[test]
+fn longley() {
+    // Each line is the response followed by the six
+    // predictors.
+    let rows: Vec<Vec<f64>> = include_str!("data/Longley.dat")
+        .lines()
+        .map(|s| s.split_whitespace().map(|v| v.parse().unwrap()).collect())
+        .collect();
+    let column = |j: usize| -> Vec<f64> { rows.iter().map(|row| row[j]).collect() };
+    let ys = column(0);
+    let predictors: Vec<Vec<f64>> = (1..7).map(column).collect();
+    let predictors: Vec<&[f64]> = predictors.iter().map(Vec::as_slice).collect();
+    let fit = stats::multiple_regression(&predictors, &ys).unwrap();
+
+    let coefficients = [
+        -3482258.63459582,
+        15.0618722713733,
+        -0.358191792925910e-1,
+        -2.02022980381683,
+        -1.03322686717359,
+        -0.511041056535807e-1,
+        1829.15146461355,
+    ];
+    let standard_errors = [
+        890420.383607373,
+        84.9149257747669,
+        0.334910077722432e-1,
+        0.488399681651699,
+        0.214274163161675,
+        0.226073200069370,
+        455.478499142212,
+    ];
+    for (i, (&got, &certified)) in fit.coefficients.iter().zip(&coefficients).enumerate() {
+        assert!(
+            lre(got, certified) >= 12.0,
+            "coefficient {} {} vs certified {}",
+            i,
+            got,
+            certified
+        );
+    }
+    for (i, (&got, &certified)) in fit.standard_errors.iter().zip(&standard_errors).enumerate() {
+        assert!(
+            lre(got, certified) >= 12.0,
+            "standard error {} {} vs certified {}",
+            i,
+            got,
+            certified
+        );
+    }
+    assert!(lre(fit.residual_standard_error, 304.854073561965) >= 12.0);
+    assert!(lre(fit.r_squared, 0.995479004577296) >= 12.0);
+    assert_eq!(9, fit.degrees_of_freedom);
+}